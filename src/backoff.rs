@@ -13,6 +13,18 @@
 //!   reducing load on failing services.
 //! - **[`FibonacciBackoff`]**: Slower growth than exponential, useful when you want
 //!   a middle ground between fixed and exponential.
+//! - **`RandomDelay`**: A uniformly random delay between a minimum and maximum.
+//!   Useful for polling loops that need spread but not growth (requires `jitter`).
+//! - **[`FromSchedule`]**: An explicit list of delays, e.g. one dictated by a server.
+//!   [`Schedule`] names any other iterator of delays as a strategy.
+//! - **[`NoDelay`]**: Retries immediately, a fixed number of times. Meant for tests.
+//! - **[`AdaptivePoll`]**: Paces a polling loop, slowing down while nothing changes
+//!   and snapping back to the minimum interval when something does.
+//! - **`WeightedBackoff`**: Picks each delay from one of several strategies at random
+//!   by weight, for experimentation (requires `jitter`).
+//!
+//! [`BackoffBuilder`] builds the fixed, exponential and Fibonacci strategies
+//! through one fluent API, returning a boxed strategy.
 //!
 //! # Examples
 //!
@@ -59,9 +71,63 @@
 //! // Backoff is automatically implemented for any Iterator<Item = Duration>
 //! // So LinearBackoff now implements Backoff!
 //! ```
+//!
+//! # Peeking
+//!
+//! The deterministic strategies ([`FixedDelay`], [`ExponentialBackoff`] and
+//! [`FibonacciBackoff`]) provide a `peek_next()` method that returns the next
+//! delay without advancing. Randomized strategies can't know their next delay
+//! before drawing it; wrap them in [`Iterator::peekable()`] instead, which
+//! draws the value early and holds on to it.
+//!
+//! # Randomness
+//!
+//! With the `jitter` feature, every randomized strategy ([`Jitter`],
+//! `JitteredExponentialBackoff`, `RandomDelay` and `WeightedBackoff`) draws
+//! its random numbers from a [`JitterSource`]. The default sources are backed
+//! by `rand`; swap in a `SeededSource`, or an implementation of your own,
+//! with `with_jitter_source()` to make the delays reproducible in tests.
+//!
+//! # Length
+//!
+//! [`ExponentialBackoff`], [`FibonacciBackoff`], [`FromSchedule`] and
+//! [`NoDelay`] implement [`ExactSizeIterator`], so a finite strategy can
+//! report how many retries it has left with [`len()`](ExactSizeIterator::len),
+//! e.g. to show "retry 3 of 5". `.take(n)` on one of them keeps that.
+//!
+//! An infinite strategy has no length: `len()` panics on an `ExponentialBackoff`
+//! or `FibonacciBackoff` without `with_max_retries()`, and [`FixedDelay`]
+//! doesn't implement `ExactSizeIterator` at all. It does report an unbounded
+//! [`size_hint()`](Iterator::size_hint), so `FixedDelay::new(d).take(n)`
+//! still has an exact `size_hint()` of `n`.
 
+use std::fmt;
+#[cfg(feature = "jitter")]
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Errors returned when constructing a backoff strategy with invalid parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BackoffError {
+    /// The base delay was [`Duration::ZERO`], which would turn the retry loop
+    /// into a busy loop.
+    ZeroDelay,
+    /// The strategy yields no delays, so the operation would never be retried.
+    NoRetries,
+}
+
+impl fmt::Display for BackoffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackoffError::ZeroDelay => write!(f, "backoff delay must be greater than zero"),
+            BackoffError::NoRetries => write!(f, "backoff strategy allows no retries"),
+        }
+    }
+}
+
+impl std::error::Error for BackoffError {}
+
 /// Trait for backoff strategies.
 ///
 /// This is implemented as an `Iterator` over `Duration`.
@@ -69,11 +135,196 @@ use std::time::Duration;
 ///
 /// You can use standard `Iterator` adapters like `.take(n)` to
 /// limit the number of retries.
-pub trait Backoff: Iterator<Item = Duration> {}
+///
+/// Adapted strategies are still strategies, so every `Iterator` adapter is a
+/// composition point. For example, [`Iterator::inspect()`] observes each
+/// delay as the strategy produces it, independently of the retry loop:
+///
+/// ```rust
+/// use async_retry::backoff::ExponentialBackoff;
+/// use std::time::Duration;
+///
+/// let strategy = ExponentialBackoff::new(Duration::from_millis(100))
+///     .with_max_retries(3)
+///     .inspect(|delay| println!("next retry in {:?}", delay));
+/// ```
+///
+/// A strategy may yield [`Duration::ZERO`] to retry immediately. With the
+/// default clock, a zero delay doesn't touch the runtime's timer; the retry
+/// loop yields to the executor once and then makes the next attempt.
+///
+/// # Implementors
+///
+/// `Backoff` is implemented for *every* `Iterator<Item = Duration>`, not just
+/// the strategies in this module. Each delay the iterator yields allows one
+/// more retry, so its length is the retry budget: `vec![d; 3].into_iter()`
+/// retries three times and then gives up, while `std::iter::repeat(d)`
+/// retries forever. Wrap a hand-built iterator in [`Schedule`] to make that
+/// intent visible where the strategy is passed to the retry.
+pub trait Backoff: Iterator<Item = Duration> {
+    /// Limits the strategy by the total time spent sleeping.
+    ///
+    /// Delays are yielded until their running sum would exceed `max`, after
+    /// which the adapter returns `None`. Unlike
+    /// [`Retry::with_max_duration()`](crate::Retry::with_max_duration), this
+    /// only counts backoff delays, not the time spent running the operation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::backoff::{Backoff, ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// // 1s + 2s + 4s = 7s; the next 8s delay would exceed 10s.
+    /// let strategy = ExponentialBackoff::new(Duration::from_secs(1))
+    ///     .take_while_total(Duration::from_secs(10));
+    /// assert_eq!(strategy.count(), 3);
+    /// ```
+    fn take_while_total(self, max: Duration) -> TakeWhileTotal<Self>
+    where
+        Self: Sized,
+    {
+        TakeWhileTotal {
+            inner: self,
+            max,
+            total: Duration::ZERO,
+            done: false,
+        }
+    }
+
+    /// Transforms every delay produced by the strategy.
+    ///
+    /// Unlike [`Iterator::map()`], which could change the item type, the
+    /// closure must return a `Duration`, so the adapter is always a `Backoff`
+    /// and keeps the strategy's length. Use it to scale, offset or clamp a
+    /// schedule.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::backoff::{Backoff, ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// let strategy = ExponentialBackoff::new(Duration::from_millis(100))
+    ///     .with_max_retries(3)
+    ///     .map_delay(|delay| delay + Duration::from_millis(50));
+    /// assert_eq!(
+    ///     strategy.collect::<Vec<_>>(),
+    ///     [150, 250, 450].map(Duration::from_millis)
+    /// );
+    /// ```
+    fn map_delay<F>(self, f: F) -> MapDelay<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Duration) -> Duration,
+    {
+        MapDelay { inner: self, f }
+    }
+
+    /// Boxes the strategy for dynamic dispatch.
+    ///
+    /// This erases the concrete type, so strategies of different types can be
+    /// stored together or returned from the same function. The boxed strategy
+    /// is still a `Backoff` and can be passed to [`Retry::new()`](crate::Retry::new).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::backoff::{Backoff, ExponentialBackoff, FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// fn pick(aggressive: bool) -> Box<dyn Backoff + Send> {
+    ///     if aggressive {
+    ///         FixedDelay::new(Duration::from_millis(10)).take(10).boxed()
+    ///     } else {
+    ///         ExponentialBackoff::new(Duration::from_millis(100)).take(5).boxed()
+    ///     }
+    /// }
+    /// ```
+    fn boxed(self) -> Box<dyn Backoff + Send>
+    where
+        Self: Sized + Send + 'static,
+    {
+        Box::new(self)
+    }
+}
 
 // Implement the trait for all types that fit the criteria.
 impl<T> Backoff for T where T: Iterator<Item = Duration> {}
 
+/// A strategy adapter that stops once the total delay would exceed a cap.
+///
+/// Created by [`Backoff::take_while_total()`].
+#[derive(Debug, Clone)]
+pub struct TakeWhileTotal<B> {
+    inner: B,
+    max: Duration,
+    total: Duration,
+    done: bool,
+}
+
+impl<B: Backoff> Iterator for TakeWhileTotal<B> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let delay = self.inner.next();
+        match delay.and_then(|d| self.total.checked_add(d)) {
+            Some(total) if total <= self.max => {
+                self.total = total;
+                delay
+            }
+            _ => {
+                // Stay exhausted, even if a later delay would still fit.
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+/// A strategy adapter that transforms every delay.
+///
+/// Created by [`Backoff::map_delay()`].
+#[derive(Clone)]
+pub struct MapDelay<B, F> {
+    inner: B,
+    f: F,
+}
+
+impl<B: fmt::Debug, F> fmt::Debug for MapDelay<B, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapDelay")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B, F> Iterator for MapDelay<B, F>
+where
+    B: Backoff,
+    F: FnMut(Duration) -> Duration,
+{
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(&mut self.f)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<B, F> ExactSizeIterator for MapDelay<B, F>
+where
+    B: Backoff + ExactSizeIterator,
+    F: FnMut(Duration) -> Duration,
+{
+}
+
 // --- Fixed Delay Strategy ---
 
 /// A backoff strategy that waits for a fixed duration.
@@ -85,9 +336,47 @@ pub struct FixedDelay {
 
 impl FixedDelay {
     /// Creates a new `FixedDelay` strategy.
-    pub fn new(duration: Duration) -> Self {
+    ///
+    /// A `duration` of [`Duration::ZERO`] is accepted and retries immediately,
+    /// which can spin a core if the operation fails fast. Use
+    /// [`try_new()`](FixedDelay::try_new) to reject zero delays.
+    pub const fn new(duration: Duration) -> Self {
         Self { duration }
     }
+
+    /// Creates a new `FixedDelay` strategy from a number of milliseconds.
+    ///
+    /// Like [`new()`](FixedDelay::new), this is a `const fn`, so strategies
+    /// can be declared as `const` or `static` items:
+    ///
+    /// ```rust
+    /// use async_retry::backoff::FixedDelay;
+    ///
+    /// const POLL_INTERVAL: FixedDelay = FixedDelay::from_millis(100);
+    /// ```
+    pub const fn from_millis(millis: u64) -> Self {
+        Self::new(Duration::from_millis(millis))
+    }
+
+    /// Creates a new `FixedDelay` strategy from a number of seconds.
+    pub const fn from_secs(secs: u64) -> Self {
+        Self::new(Duration::from_secs(secs))
+    }
+
+    /// Creates a new `FixedDelay` strategy, rejecting a zero `duration`.
+    ///
+    /// Returns [`BackoffError::ZeroDelay`] if `duration` is [`Duration::ZERO`].
+    pub const fn try_new(duration: Duration) -> Result<Self, BackoffError> {
+        if duration.is_zero() {
+            return Err(BackoffError::ZeroDelay);
+        }
+        Ok(Self::new(duration))
+    }
+
+    /// Returns the next delay without advancing the strategy.
+    pub fn peek_next(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
 }
 
 impl Iterator for FixedDelay {
@@ -96,6 +385,10 @@ impl Iterator for FixedDelay {
     fn next(&mut self) -> Option<Self::Item> {
         Some(self.duration)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
 }
 
 // --- Exponential Backoff Strategy ---
@@ -103,9 +396,16 @@ impl Iterator for FixedDelay {
 /// A backoff strategy that doubles the wait duration.
 ///
 /// Example: 100ms, 200ms, 400ms, 800ms...
+///
+/// The growth factor can be changed with
+/// [`with_integer_multiplier()`](ExponentialBackoff::with_integer_multiplier)
+/// or [`with_multiplier()`](ExponentialBackoff::with_multiplier).
 #[derive(Debug, Clone, Copy)]
 pub struct ExponentialBackoff {
+    base_delay: Duration,
     current: Duration,
+    multiplier: Multiplier,
+    first_delay: Option<Duration>,
     max_delay: Option<Duration>,
     max_retries: Option<usize>,
     attempt: usize,
@@ -115,66 +415,287 @@ impl ExponentialBackoff {
     /// Creates a new `ExponentialBackoff` strategy.
     ///
     /// - `base_delay`: The initial delay (e.g., 100ms).
-    pub fn new(base_delay: Duration) -> Self {
+    ///
+    /// A `base_delay` of [`Duration::ZERO`] never grows, so every retry happens
+    /// immediately. Use [`try_new()`](ExponentialBackoff::try_new) to reject it.
+    ///
+    /// A zero base is kept as-is rather than silently promoted to a minimum,
+    /// since immediate retries are occasionally intended (e.g., in tests). Pair
+    /// it with [`with_max_retries()`](ExponentialBackoff::with_max_retries) to
+    /// avoid retrying in a tight loop.
+    pub const fn new(base_delay: Duration) -> Self {
         Self {
+            base_delay,
             current: base_delay,
+            multiplier: Multiplier::Integer(2),
+            first_delay: None,
             max_delay: None,
             max_retries: None,
             attempt: 0,
         }
     }
 
+    /// Creates a new `ExponentialBackoff` strategy with a base delay in
+    /// milliseconds.
+    ///
+    /// Shorthand for `ExponentialBackoff::new(Duration::from_millis(millis))`.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self::new(Duration::from_millis(millis))
+    }
+
+    /// Creates a new `ExponentialBackoff` strategy with a base delay in
+    /// seconds.
+    ///
+    /// Shorthand for `ExponentialBackoff::new(Duration::from_secs(secs))`.
+    pub const fn from_secs(secs: u64) -> Self {
+        Self::new(Duration::from_secs(secs))
+    }
+
+    /// Creates a new `ExponentialBackoff` strategy, rejecting a zero `base_delay`.
+    ///
+    /// Returns [`BackoffError::ZeroDelay`] if `base_delay` is [`Duration::ZERO`].
+    pub const fn try_new(base_delay: Duration) -> Result<Self, BackoffError> {
+        if base_delay.is_zero() {
+            return Err(BackoffError::ZeroDelay);
+        }
+        Ok(Self::new(base_delay))
+    }
+
+    /// Creates a truncated `ExponentialBackoff` that grows from `base_delay`
+    /// until it reaches `max_delay`, then stays at exactly `max_delay`.
+    ///
+    /// This is shorthand for `ExponentialBackoff::new(base_delay).with_max_delay(max_delay)`.
+    pub const fn truncated(base_delay: Duration, max_delay: Duration) -> Self {
+        Self::new(base_delay).with_max_delay(max_delay)
+    }
+
+    /// Creates an `ExponentialBackoff` whose `retries` delays add up to about
+    /// `total`.
+    ///
+    /// The delays double as usual, so the base delay is chosen as
+    /// `total / (2^retries - 1)` and the strategy is limited to `retries`
+    /// retries. With a 3.1s total over 5 retries, the delays are 100ms,
+    /// 200ms, 400ms, 800ms and 1.6s.
+    ///
+    /// The fit is approximate: the base delay is computed in floating point
+    /// and rounded to whole nanoseconds, and every later delay is a multiple
+    /// of it, so the sum can be off by a few nanoseconds per retry. With many
+    /// retries the base delay becomes tiny, and once `2^retries` exceeds
+    /// `total` in nanoseconds it rounds to zero. Only the backoff delays are
+    /// budgeted, not the time spent running the operation; use
+    /// [`Retry::with_max_duration()`](crate::Retry::with_max_duration) for a
+    /// hard limit. A `retries` of `0` yields no delays at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::backoff::ExponentialBackoff;
+    /// use std::time::Duration;
+    ///
+    /// let strategy = ExponentialBackoff::to_fit(Duration::from_secs(30), 6);
+    /// let total: Duration = strategy.sum();
+    /// let target = Duration::from_secs(30);
+    /// assert!(total.max(target) - total.min(target) < Duration::from_micros(1));
+    /// ```
+    pub fn to_fit(total: Duration, retries: usize) -> Self {
+        let exponent = i32::try_from(retries).unwrap_or(i32::MAX);
+        let base_delay =
+            Duration::try_from_secs_f64(total.as_secs_f64() / (2f64.powi(exponent) - 1.0))
+                .unwrap_or(total);
+        Self::new(base_delay).with_max_retries(retries)
+    }
+
     /// Sets an optional maximum delay.
     /// The backoff will not increase beyond this duration.
-    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+    ///
+    /// Once the cap is reached, the internal delay stops growing, so every
+    /// later retry yields exactly `max_delay`.
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
         self.max_delay = Some(max_delay);
         self
     }
 
+    /// Sets the first delay independently of the growth base.
+    ///
+    /// The first retry waits `first_delay`; later retries follow the usual
+    /// sequence from `base_delay`, as if the base had been yielded first. With
+    /// a 1s first delay and a 100ms base, the delays are 1s, 200ms, 400ms, ...
+    ///
+    /// The first delay is capped by [`with_max_delay()`](ExponentialBackoff::with_max_delay)
+    /// like any other, but doesn't affect when the growth reaches the cap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::backoff::ExponentialBackoff;
+    /// use std::time::Duration;
+    ///
+    /// let strategy = ExponentialBackoff::new(Duration::from_millis(100))
+    ///     .with_first_delay(Duration::from_secs(1))
+    ///     .with_max_retries(4);
+    /// assert_eq!(
+    ///     strategy.collect::<Vec<_>>(),
+    ///     [1000, 200, 400, 800].map(Duration::from_millis)
+    /// );
+    /// ```
+    pub const fn with_first_delay(mut self, first_delay: Duration) -> Self {
+        self.first_delay = Some(first_delay);
+        self
+    }
+
+    /// Applies full jitter to every delay, keeping a named strategy type.
+    ///
+    /// Unlike wrapping in [`Jitter::new()`], the result still offers
+    /// `with_max_delay()` and `with_max_retries()`, so limits can be set
+    /// before or after jittering. It requires the `jitter` feature flag.
+    #[cfg(feature = "jitter")]
+    pub const fn jittered(self) -> JitteredExponentialBackoff {
+        JitteredExponentialBackoff {
+            inner: self,
+            source: ThreadRngSource,
+        }
+    }
+
+    /// Multiplies the delay by `multiplier` after each retry, instead of 2.
+    ///
+    /// The multiplication is done on whole nanoseconds with saturating
+    /// arithmetic, so the progression is exact and identical on every
+    /// platform: a multiplier of 3 from 100ms yields 100ms, 300ms, 900ms, ...
+    pub const fn with_integer_multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = Multiplier::Integer(multiplier);
+        self
+    }
+
+    /// Multiplies the delay by a fractional `multiplier` after each retry,
+    /// instead of 2.
+    ///
+    /// This uses floating-point arithmetic, so delays may be rounded by up to
+    /// a nanosecond. Prefer
+    /// [`with_integer_multiplier()`](ExponentialBackoff::with_integer_multiplier)
+    /// for whole-number factors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `multiplier` is negative or not finite.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        assert!(
+            multiplier.is_finite() && multiplier >= 0.0,
+            "ExponentialBackoff: multiplier must be finite and non-negative"
+        );
+        self.multiplier = Multiplier::Float(multiplier);
+        self
+    }
+
     /// Sets an optional maximum number of retries.
-    /// The iterator will return `None` after this many attempts.
+    /// The iterator will return `None` after yielding this many delays.
     /// Fulfills requirement from API Example 1.
-    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+    ///
+    /// This counts retries, not attempts: the operation runs at most
+    /// `max_retries + 1` times. A `max_retries` of `0` means a single attempt
+    /// with no retries. To count the first attempt too, use
+    /// [`Retry::with_max_attempts()`](crate::Retry::with_max_attempts).
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
         self.max_retries = Some(max_retries);
         self
     }
-}
 
-impl Iterator for ExponentialBackoff {
-    type Item = Duration;
+    /// Restarts the strategy from its base delay with a fresh retry count.
+    ///
+    /// [`Retry::run_with()`](crate::Retry::run_with) doesn't need this, as it always
+    /// drives a fresh clone of the configured strategy.
+    pub fn reset(&mut self) {
+        self.current = self.base_delay;
+        self.attempt = 0;
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Returns the next delay without advancing the strategy.
+    pub fn peek_next(&self) -> Option<Duration> {
         // Check max retries limit
         if let Some(max_retries) = self.max_retries {
             if self.attempt >= max_retries {
                 return None;
             }
         }
-        self.attempt += 1;
 
-        // Get the current delay
-        let mut delay = self.current;
+        // The first delay may be set apart from the growth sequence
+        let delay = match self.first_delay {
+            Some(first_delay) if self.attempt == 0 => first_delay,
+            _ => self.current,
+        };
 
         // Apply max delay cap
-        if let Some(max_delay) = self.max_delay {
-            delay = delay.min(max_delay);
+        match self.max_delay {
+            Some(max_delay) => Some(delay.min(max_delay)),
+            None => Some(delay),
         }
+    }
+}
+
+impl Iterator for ExponentialBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get the current delay, respecting the retry limit and delay cap
+        let delay = self.peek_next()?;
+        self.attempt += 1;
 
-        // Calculate next duration
+        // Calculate next duration, unless we've already reached the cap.
         // We use saturating_mul to prevent panic on overflow.
-        self.current = self.current.saturating_mul(2);
+        if self
+            .max_delay
+            .map_or(true, |max_delay| self.current < max_delay)
+        {
+            self.current = self.multiplier.apply(self.current);
+        }
 
         Some(delay)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        remaining_retries(self.max_retries, self.attempt)
+    }
+}
+
+/// # Panics
+///
+/// [`len()`](ExactSizeIterator::len) panics if no
+/// [`with_max_retries()`](ExponentialBackoff::with_max_retries) limit is set,
+/// as the strategy is then infinite.
+impl ExactSizeIterator for ExponentialBackoff {}
+
+/// The factor an [`ExponentialBackoff`] grows its delay by.
+#[derive(Debug, Clone, Copy)]
+enum Multiplier {
+    /// Exact multiplication on nanoseconds.
+    Integer(u32),
+    /// Floating-point multiplication, for fractional factors.
+    Float(f64),
+}
+
+impl Multiplier {
+    fn apply(self, delay: Duration) -> Duration {
+        match self {
+            Multiplier::Integer(factor) => delay.saturating_mul(factor),
+            // Saturate rather than panic if the scaled delay overflows.
+            Multiplier::Float(factor) => {
+                Duration::try_from_secs_f64(delay.as_secs_f64() * factor).unwrap_or(Duration::MAX)
+            }
+        }
+    }
 }
 
-/// --- Fibonacci Backoff Strategy ---
+// --- Fibonacci Backoff Strategy ---
 
 /// A backoff strategy based on the Fibonacci sequence.
 ///
 /// Example: 1s, 1s, 2s, 3s, 5s, 8s...
+///
+/// The sequence is computed with saturating arithmetic, so long-running
+/// strategies never panic or wrap: once the next delay would overflow, it is
+/// pinned at [`Duration::MAX`] (or `max_delay`, if set) for every later retry.
 #[derive(Debug, Clone, Copy)]
 pub struct FibonacciBackoff {
+    base_delay: Duration,
     current: Duration,
     next: Duration,
     max_delay: Option<Duration>,
@@ -186,8 +707,9 @@ impl FibonacciBackoff {
     /// Creates a new `FibonacciBackoff`.
     ///
     /// - `base_delay`: The duration for the first two retries (e.g., 1s).
-    pub fn new(base_delay: Duration) -> Self {
+    pub const fn new(base_delay: Duration) -> Self {
         Self {
+            base_delay,
             current: base_delay,
             next: base_delay,
             max_delay: None,
@@ -196,112 +718,1389 @@ impl FibonacciBackoff {
         }
     }
 
+    /// Creates a new `FibonacciBackoff` with a base delay in milliseconds.
+    ///
+    /// Shorthand for `FibonacciBackoff::new(Duration::from_millis(millis))`.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self::new(Duration::from_millis(millis))
+    }
+
+    /// Creates a new `FibonacciBackoff` with a base delay in seconds.
+    ///
+    /// Shorthand for `FibonacciBackoff::new(Duration::from_secs(secs))`.
+    pub const fn from_secs(secs: u64) -> Self {
+        Self::new(Duration::from_secs(secs))
+    }
+
     /// Sets an optional maximum delay.
-    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+    ///
+    /// Once the sequence reaches `max_delay`, it stops advancing and every
+    /// later retry waits exactly `max_delay`.
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
         self.max_delay = Some(max_delay);
         self
     }
 
     /// Sets an optional maximum number of retries.
-    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+    ///
+    /// Like [`ExponentialBackoff::with_max_retries()`], this doesn't count
+    /// the first attempt, so the operation runs at most `max_retries + 1`
+    /// times.
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
         self.max_retries = Some(max_retries);
         self
     }
-}
 
-impl Iterator for FibonacciBackoff {
-    type Item = Duration;
+    /// Restarts the sequence from its base delay with a fresh retry count.
+    ///
+    /// [`Retry::run_with()`](crate::Retry::run_with) doesn't need this, as it always
+    /// drives a fresh clone of the configured strategy.
+    pub fn reset(&mut self) {
+        self.current = self.base_delay;
+        self.next = self.base_delay;
+        self.attempt = 0;
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Returns the next delay without advancing the strategy.
+    pub fn peek_next(&self) -> Option<Duration> {
         // Check max retries limit
         if let Some(max_retries) = self.max_retries {
             if self.attempt >= max_retries {
                 return None;
             }
         }
-        self.attempt += 1;
-
-        // Get the current delay
-        let mut delay = self.current;
 
         // Apply max delay cap
-        if let Some(max_delay) = self.max_delay {
-            delay = delay.min(max_delay);
+        match self.max_delay {
+            Some(max_delay) => Some(self.current.min(max_delay)),
+            None => Some(self.current),
         }
+    }
+}
+
+impl Iterator for FibonacciBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get the current delay, respecting the retry limit and delay cap
+        let delay = self.peek_next()?;
+        self.attempt += 1;
 
-        // Calculate next duration
-        let new_next = self.current.saturating_add(self.next);
-        self.current = self.next;
-        self.next = new_next;
+        // Calculate next duration, unless we've already reached the cap.
+        if self
+            .max_delay
+            .map_or(true, |max_delay| self.current < max_delay)
+        {
+            let new_next = self.current.saturating_add(self.next);
+            self.current = self.next;
+            self.next = new_next;
+        }
 
         Some(delay)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        remaining_retries(self.max_retries, self.attempt)
+    }
 }
 
-// --- Jitter (Future Work) ---
+/// # Panics
+///
+/// [`len()`](ExactSizeIterator::len) panics if no
+/// [`with_max_retries()`](FibonacciBackoff::with_max_retries) limit is set,
+/// as the strategy is then infinite.
+impl ExactSizeIterator for FibonacciBackoff {}
 
-/// A wrapper that adds random jitter to any `Backoff` strategy.
+/// The `size_hint()` of a strategy with an optional retry limit.
+fn remaining_retries(max_retries: Option<usize>, attempt: usize) -> (usize, Option<usize>) {
+    match max_retries {
+        Some(max_retries) => {
+            let remaining = max_retries.saturating_sub(attempt);
+            (remaining, Some(remaining))
+        }
+        None => (usize::MAX, None),
+    }
+}
+
+// --- Explicit Schedule ---
+
+/// A backoff strategy that follows an explicit list of delays.
 ///
-/// This is crucial for production systems to prevent the "thundering herd"
-/// problem. It requires the `jitter` feature flag.
-#[cfg(feature = "jitter")]
+/// Each delay is yielded once, in order, and then the strategy is exhausted.
+/// This suits servers that dictate the exact retry schedule, and composes
+/// with wrappers such as `Jitter` like any other strategy.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::backoff::FromSchedule;
+/// use std::time::Duration;
+///
+/// let mut strategy = FromSchedule::new(vec![
+///     Duration::from_millis(100),
+///     Duration::from_secs(1),
+/// ]);
+/// assert_eq!(strategy.next(), Some(Duration::from_millis(100)));
+/// assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
+/// assert_eq!(strategy.next(), None);
+/// ```
 #[derive(Debug, Clone)]
-pub struct Jitter<B: Backoff> {
-    inner: B,
+pub struct FromSchedule {
+    delays: std::vec::IntoIter<Duration>,
 }
 
-#[cfg(feature = "jitter")]
-impl<B: Backoff> Jitter<B> {
-    /// Wraps a `Backoff` strategy to add full jitter.
-    ///
-    /// The jitter applied is a random duration between 0 and the
-    /// duration provided by the inner strategy.
-    pub fn new(inner: B) -> Self {
-        Self { inner }
+impl FromSchedule {
+    /// Creates a new `FromSchedule` strategy that yields `delays` in order.
+    pub fn new(delays: impl IntoIterator<Item = Duration>) -> Self {
+        Self {
+            delays: delays.into_iter().collect::<Vec<_>>().into_iter(),
+        }
+    }
+
+    /// Returns the next delay without advancing the strategy.
+    pub fn peek_next(&self) -> Option<Duration> {
+        self.delays.as_slice().first().copied()
     }
 }
 
-#[cfg(feature = "jitter")]
-impl<B: Backoff> Iterator for Jitter<B> {
+impl Iterator for FromSchedule {
     type Item = Duration;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next().map(|duration| {
-            use rand::Rng;
-            let mut rng = rand::thread_rng();
-            // Apply full jitter: 0..=duration
-            let jitter_millis = rng.gen_range(0..=duration.as_millis());
-            Duration::from_millis(jitter_millis as u64)
-        })
+        self.delays.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.delays.size_hint()
     }
 }
 
-// --- Unit Tests (as required by persona) ---
+impl ExactSizeIterator for FromSchedule {}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Any iterator of delays, named as a backoff strategy.
+///
+/// Every `Iterator<Item = Duration>` is already a [`Backoff`], so this adds
+/// no behavior. It exists for clarity: passing `Schedule::new(iter)` instead
+/// of a bare `iter` states that its delays *are* the retry schedule, one retry
+/// per delay, and that it ends when the iterator does. Unlike
+/// [`FromSchedule`], it doesn't collect the delays up front, so it also wraps
+/// lazy or infinite iterators.
+///
+/// A `Schedule` can also be collected from delays with
+/// [`FromIterator`], e.g. `Schedule::from_iter(slice.iter().copied())`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::backoff::Schedule;
+/// use std::time::Duration;
+///
+/// let delays = [Duration::from_millis(100), Duration::from_millis(500)];
+/// let strategy = Schedule::from_iter(delays.iter().copied());
+/// assert_eq!(strategy.len(), 2);
+///
+/// // Endless polling, spelled out
+/// let forever = Schedule::new(std::iter::repeat(Duration::from_secs(1)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schedule<I> {
+    delays: I,
+}
 
-    #[test]
-    fn test_fixed_delay() {
-        let mut strategy = FixedDelay::new(Duration::from_secs(1)).take(3);
-        assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
-        assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
-        assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
-        assert_eq!(strategy.next(), None);
+impl<I> Schedule<I>
+where
+    I: Iterator<Item = Duration>,
+{
+    /// Creates a new `Schedule` that yields the delays of `delays` in order.
+    pub fn new(delays: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            delays: delays.into_iter(),
+        }
     }
 
-    #[test]
-    fn test_exponential_backoff() {
-        let mut strategy = ExponentialBackoff::new(Duration::from_millis(100)).take(4);
-        assert_eq!(strategy.next(), Some(Duration::from_millis(100)));
-        assert_eq!(strategy.next(), Some(Duration::from_millis(200)));
-        assert_eq!(strategy.next(), Some(Duration::from_millis(400)));
-        assert_eq!(strategy.next(), Some(Duration::from_millis(800)));
-        assert_eq!(strategy.next(), None);
+    /// Returns the wrapped iterator.
+    pub fn into_inner(self) -> I {
+        self.delays
     }
+}
 
-    #[test]
+impl FromIterator<Duration> for Schedule<std::vec::IntoIter<Duration>> {
+    fn from_iter<T: IntoIterator<Item = Duration>>(delays: T) -> Self {
+        Self::new(delays.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<I> Iterator for Schedule<I>
+where
+    I: Iterator<Item = Duration>,
+{
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.delays.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.delays.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Schedule<I> where I: ExactSizeIterator<Item = Duration> {}
+
+// --- No Delay Strategy ---
+
+/// A backoff strategy that retries immediately, for tests.
+///
+/// Every delay is [`Duration::ZERO`], which the retry loop handles by yielding
+/// to the executor once instead of arming a timer, so tests exercise the full
+/// loop without waiting on the wall clock. The strategy is always finite,
+/// which avoids the busy loop that `FixedDelay::new(Duration::ZERO)` risks
+/// without a `.take()`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::backoff::NoDelay;
+/// use std::time::Duration;
+///
+/// let mut strategy = NoDelay::with_max_retries(2);
+/// assert_eq!(strategy.next(), Some(Duration::ZERO));
+/// assert_eq!(strategy.next(), Some(Duration::ZERO));
+/// assert_eq!(strategy.next(), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NoDelay {
+    remaining: usize,
+}
+
+impl NoDelay {
+    /// Creates a new `NoDelay` strategy allowing `max_retries` retries.
+    pub const fn with_max_retries(max_retries: usize) -> Self {
+        Self {
+            remaining: max_retries,
+        }
+    }
+}
+
+impl Iterator for NoDelay {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        Some(Duration::ZERO)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for NoDelay {}
+
+// --- Adaptive Polling ---
+
+/// A polling interval that grows while the polled state stays the same.
+///
+/// This paces a long-running poll loop rather than backing off from
+/// failures: while each poll reports no change (e.g., a healthy resource, or
+/// a job that isn't ready), the interval doubles from `min_interval` up to
+/// `max_interval`. As soon as a poll reports a change, it drops back to
+/// `min_interval` so the next transition is noticed quickly.
+///
+/// Report each poll with [`observe()`](AdaptivePoll::observe), which returns
+/// the delay before the next one. Used as a plain [`Backoff`], every delay
+/// counts as "no change", so it grows to the cap and stays there forever;
+/// bound it with `.take()` if needed.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::backoff::AdaptivePoll;
+/// use std::time::Duration;
+///
+/// let mut pacing = AdaptivePoll::new(Duration::from_secs(1), Duration::from_secs(4));
+/// assert_eq!(pacing.observe(false), Duration::from_secs(1));
+/// assert_eq!(pacing.observe(false), Duration::from_secs(2));
+/// assert_eq!(pacing.observe(false), Duration::from_secs(4));
+/// assert_eq!(pacing.observe(false), Duration::from_secs(4));
+/// // The state changed: poll quickly again
+/// assert_eq!(pacing.observe(true), Duration::from_secs(1));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptivePoll {
+    min_interval: Duration,
+    max_interval: Duration,
+    current: Duration,
+}
+
+impl AdaptivePoll {
+    /// Creates a new `AdaptivePoll` pacing between `min_interval` and
+    /// `max_interval`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_interval` is zero, since it could never grow, or if it
+    /// is greater than `max_interval`.
+    pub const fn new(min_interval: Duration, max_interval: Duration) -> Self {
+        assert!(
+            !min_interval.is_zero(),
+            "AdaptivePoll: min_interval must be non-zero"
+        );
+        assert!(
+            min_interval.as_nanos() <= max_interval.as_nanos(),
+            "AdaptivePoll: min_interval must not exceed max_interval"
+        );
+        Self {
+            min_interval,
+            max_interval,
+            current: min_interval,
+        }
+    }
+
+    /// Records the outcome of a poll and returns the delay before the next.
+    ///
+    /// Pass `true` if the state changed since the previous poll. That resets
+    /// the interval to `min_interval`; otherwise the current interval is
+    /// returned and the next one doubles, up to `max_interval`.
+    pub fn observe(&mut self, changed: bool) -> Duration {
+        if changed {
+            self.reset();
+        }
+        let delay = self.current;
+        self.current = self.current.saturating_mul(2).min(self.max_interval);
+        delay
+    }
+
+    /// Returns the delay the next unchanged poll would get, without
+    /// advancing.
+    pub fn peek_next(&self) -> Duration {
+        self.current
+    }
+
+    /// Drops the interval back to `min_interval`.
+    pub fn reset(&mut self) {
+        self.current = self.min_interval;
+    }
+}
+
+impl Iterator for AdaptivePoll {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.observe(false))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+// --- Jitter ---
+
+/// A wrapper that adds random jitter to any `Backoff` strategy.
+///
+/// This is crucial for production systems to prevent the "thundering herd"
+/// problem. It requires the `jitter` feature flag.
+#[cfg(feature = "jitter")]
+#[derive(Clone)]
+pub struct Jitter<B: Backoff, R = ThreadRngSource> {
+    inner: B,
+    mode: JitterMode,
+    observer: Option<JitterObserver>,
+    remaining: Option<usize>,
+    source: R,
+}
+
+/// A callback receiving each nominal delay and its jittered replacement.
+#[cfg(feature = "jitter")]
+type JitterObserver = Arc<dyn Fn(Duration, Duration) + Send + Sync>;
+
+#[cfg(feature = "jitter")]
+impl<B: Backoff + fmt::Debug, R> fmt::Debug for Jitter<B, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Jitter")
+            .field("inner", &self.inner)
+            .field("mode", &self.mode)
+            .field("observer", &self.observer.is_some())
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+/// How a [`Jitter`] wrapper randomizes the inner strategy's delays.
+#[cfg(feature = "jitter")]
+#[derive(Debug, Clone, Copy)]
+enum JitterMode {
+    /// A random duration in `0..=d`.
+    Full,
+    /// A random duration in `d * min_factor..=d * max_factor`.
+    Bounded(f64, f64),
+}
+
+#[cfg(feature = "jitter")]
+impl<B: Backoff> Jitter<B> {
+    /// Wraps a `Backoff` strategy to add full jitter.
+    ///
+    /// The jitter applied is a random duration between 0 and the
+    /// duration provided by the inner strategy.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            mode: JitterMode::Full,
+            observer: None,
+            remaining: None,
+            source: ThreadRngSource,
+        }
+    }
+
+    /// Wraps a `Backoff` strategy to add jitter proportional to each delay.
+    ///
+    /// Each delay `d` from the inner strategy is scaled by a random factor in
+    /// `1 - fraction..=1 + fraction`, so a `fraction` of `0.2` yields delays
+    /// within ±20% of `d`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` is not within `[0, 1]`.
+    pub fn proportional(inner: B, fraction: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "Jitter: fraction must be within [0, 1]"
+        );
+        Self {
+            inner,
+            mode: JitterMode::Bounded(1.0 - fraction, 1.0 + fraction),
+            observer: None,
+            remaining: None,
+            source: ThreadRngSource,
+        }
+    }
+
+    /// Wraps a `Backoff` strategy to scale each delay by a random factor in
+    /// `min_factor..=max_factor`.
+    ///
+    /// This generalizes the other modes: `bounded(inner, 0.9, 1.3)` yields
+    /// delays between 90% and 130% of nominal, `bounded(inner, 0.5, 1.0)` is
+    /// "equal jitter", and [`proportional()`](Jitter::proportional) is
+    /// `bounded(inner, 1.0 - fraction, 1.0 + fraction)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either factor is negative or not finite, or if `min_factor`
+    /// is greater than `max_factor`.
+    pub fn bounded(inner: B, min_factor: f64, max_factor: f64) -> Self {
+        assert!(
+            min_factor >= 0.0 && max_factor.is_finite(),
+            "Jitter: factors must be finite and non-negative"
+        );
+        assert!(
+            min_factor <= max_factor,
+            "Jitter: min_factor must not be greater than max_factor"
+        );
+        Self {
+            inner,
+            mode: JitterMode::Bounded(min_factor, max_factor),
+            observer: None,
+            remaining: None,
+            source: ThreadRngSource,
+        }
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl<B: Backoff, R: JitterSource> Jitter<B, R> {
+    /// Draws the random numbers for jitter from `source`.
+    ///
+    /// The default is [`ThreadRngSource`]. A [`SeededSource`] makes the
+    /// jittered delays reproducible, e.g. in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::backoff::{ExponentialBackoff, Jitter, SeededSource};
+    /// use std::time::Duration;
+    ///
+    /// let strategy = || {
+    ///     Jitter::new(ExponentialBackoff::new(Duration::from_millis(100)))
+    ///         .with_jitter_source(SeededSource::new(42))
+    ///         .take(5)
+    /// };
+    /// assert_eq!(strategy().collect::<Vec<_>>(), strategy().collect::<Vec<_>>());
+    /// ```
+    pub fn with_jitter_source<R2: JitterSource>(self, source: R2) -> Jitter<B, R2> {
+        Jitter {
+            inner: self.inner,
+            mode: self.mode,
+            observer: self.observer,
+            remaining: self.remaining,
+            source,
+        }
+    }
+
+    /// Reports every delay before and after jitter is applied.
+    ///
+    /// `observer` is called with `(nominal, jittered)` each time a delay is
+    /// yielded, where `nominal` is the inner strategy's delay and `jittered`
+    /// is the delay the retry loop will actually sleep. The retry loop only
+    /// ever sees the jittered value, so this is the place to log or record
+    /// how far jitter deviates from the nominal schedule.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::backoff::{ExponentialBackoff, Jitter};
+    /// use std::time::Duration;
+    ///
+    /// let strategy = Jitter::new(ExponentialBackoff::new(Duration::from_millis(100)))
+    ///     .with_observer(|nominal, jittered| {
+    ///         println!("nominal {:?}, jittered {:?}", nominal, jittered);
+    ///     });
+    /// ```
+    pub fn with_observer<F>(mut self, observer: F) -> Self
+    where
+        F: Fn(Duration, Duration) + Send + Sync + 'static,
+    {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Sets an optional maximum number of retries.
+    /// The iterator will return `None` after yielding this many delays.
+    ///
+    /// This limits the jittered strategy itself, like `.take(max_retries)`,
+    /// but keeps the `Jitter` type so other settings can still be chained. If
+    /// the inner strategy has a limit of its own, the lower one wins.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.remaining = Some(max_retries);
+        self
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl<B: Backoff, R: JitterSource> Iterator for Jitter<B, R> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        let mode = self.mode;
+        let nominal = self.inner.next()?;
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        let jittered = match mode {
+            JitterMode::Full => self.source.next_duration(Duration::ZERO, nominal),
+            JitterMode::Bounded(min_factor, max_factor) => {
+                let factor = min_factor + (max_factor - min_factor) * unit(&mut self.source);
+                // Saturate rather than panic if the scaled delay overflows.
+                Duration::try_from_secs_f64(nominal.as_secs_f64() * factor).unwrap_or(Duration::MAX)
+            }
+        };
+        if let Some(observer) = &self.observer {
+            observer(nominal, jittered);
+        }
+        Some(jittered)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        match self.remaining {
+            Some(remaining) => (
+                lower.min(remaining),
+                Some(upper.map_or(remaining, |upper| upper.min(remaining))),
+            ),
+            None => (lower, upper),
+        }
+    }
+}
+
+// --- Random Sources ---
+
+/// A source of randomness for the jittered strategies.
+///
+/// [`Jitter`], [`JitteredExponentialBackoff`], [`RandomDelay`] and
+/// [`WeightedBackoff`] draw all of their random numbers through this trait,
+/// so a single implementation controls the randomness of any of them. Use
+/// [`ThreadRngSource`] (the default for most strategies) in production, and a
+/// [`SeededSource`] or a custom implementation for deterministic tests. It
+/// requires the `jitter` feature flag.
+///
+/// # Examples
+///
+/// A source that always picks the midpoint:
+///
+/// ```rust
+/// use async_retry::backoff::{FixedDelay, Jitter, JitterSource};
+/// use std::time::Duration;
+///
+/// struct Midpoint;
+///
+/// impl JitterSource for Midpoint {
+///     fn next_f64(&mut self) -> f64 {
+///         0.5
+///     }
+/// }
+///
+/// let mut strategy = Jitter::new(FixedDelay::new(Duration::from_secs(1)))
+///     .with_jitter_source(Midpoint);
+/// assert_eq!(strategy.next(), Some(Duration::from_millis(500)));
+/// ```
+#[cfg(feature = "jitter")]
+pub trait JitterSource {
+    /// Returns a random value in `[0, 1]`.
+    ///
+    /// Values outside `[0, 1]` are clamped by the strategies, and `NaN` is
+    /// treated as `0`.
+    fn next_f64(&mut self) -> f64;
+
+    /// Returns a random duration in `min..=max`, or `min` if `max` is smaller.
+    ///
+    /// The default implementation scales the range by
+    /// [`next_f64()`](JitterSource::next_f64), so `max` is returned for `1.0`.
+    /// [`ThreadRngSource`] and [`SeededSource`] override it to draw a whole
+    /// number of nanoseconds uniformly from the range, which keeps full
+    /// precision for long delays.
+    fn next_duration(&mut self, min: Duration, max: Duration) -> Duration {
+        min + max.saturating_sub(min).mul_f64(unit(self))
+    }
+}
+
+/// Draws a value from `source`, clamped to `[0, 1]`.
+#[cfg(feature = "jitter")]
+fn unit<R: JitterSource + ?Sized>(source: &mut R) -> f64 {
+    let value = source.next_f64();
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+/// A [`JitterSource`] backed by `rand`'s thread-local generator.
+///
+/// This is the default source of [`Jitter`], [`JitteredExponentialBackoff`]
+/// and [`RandomDelay`]. It requires the `jitter` feature flag.
+#[cfg(feature = "jitter")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadRngSource;
+
+#[cfg(feature = "jitter")]
+impl JitterSource for ThreadRngSource {
+    fn next_f64(&mut self) -> f64 {
+        use rand::Rng;
+        rand::thread_rng().gen()
+    }
+
+    fn next_duration(&mut self, min: Duration, max: Duration) -> Duration {
+        use rand::Rng;
+        rand::thread_rng().gen_range(min..=max.max(min))
+    }
+}
+
+/// A seedable [`JitterSource`], for reproducible delays.
+///
+/// Two sources created with the same seed produce the same sequence of
+/// values. This is the default source of [`WeightedBackoff`], seeded from
+/// the operating system. It requires the `jitter` feature flag.
+#[cfg(feature = "jitter")]
+#[derive(Debug, Clone)]
+pub struct SeededSource {
+    rng: rand::rngs::StdRng,
+}
+
+#[cfg(feature = "jitter")]
+impl SeededSource {
+    /// Creates a source that produces a fixed sequence for `seed`.
+    pub fn new(seed: u64) -> Self {
+        use rand::SeedableRng;
+        Self {
+            rng: rand::rngs::StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Creates a source seeded from the operating system's randomness.
+    pub fn from_entropy() -> Self {
+        use rand::SeedableRng;
+        Self {
+            rng: rand::rngs::StdRng::from_entropy(),
+        }
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl JitterSource for SeededSource {
+    fn next_f64(&mut self) -> f64 {
+        use rand::Rng;
+        self.rng.gen()
+    }
+
+    fn next_duration(&mut self, min: Duration, max: Duration) -> Duration {
+        use rand::Rng;
+        self.rng.gen_range(min..=max.max(min))
+    }
+}
+
+/// An [`ExponentialBackoff`] with full jitter applied to every delay.
+///
+/// Created by [`ExponentialBackoff::jittered()`]. It behaves like
+/// `Jitter::new(exponential)`, but keeps a named type whose limits can still
+/// be set after jittering. Each delay is drawn from `0..=d`, where `d` is the
+/// capped exponential delay. It requires the `jitter` feature flag.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::backoff::ExponentialBackoff;
+/// use std::time::Duration;
+///
+/// let strategy = ExponentialBackoff::new(Duration::from_millis(100))
+///     .jittered()
+///     .with_max_delay(Duration::from_secs(30))
+///     .with_max_retries(10);
+/// assert_eq!(strategy.len(), 10);
+/// ```
+#[cfg(feature = "jitter")]
+#[derive(Debug, Clone, Copy)]
+pub struct JitteredExponentialBackoff<R = ThreadRngSource> {
+    inner: ExponentialBackoff,
+    source: R,
+}
+
+#[cfg(feature = "jitter")]
+impl<R: JitterSource> JitteredExponentialBackoff<R> {
+    /// Draws the random numbers for jitter from `source`, as
+    /// [`Jitter::with_jitter_source()`].
+    pub fn with_jitter_source<R2: JitterSource>(
+        self,
+        source: R2,
+    ) -> JitteredExponentialBackoff<R2> {
+        JitteredExponentialBackoff {
+            inner: self.inner,
+            source,
+        }
+    }
+
+    /// Caps the delay before jitter is applied, as
+    /// [`ExponentialBackoff::with_max_delay()`].
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.inner = self.inner.with_max_delay(max_delay);
+        self
+    }
+
+    /// Sets an optional maximum number of retries, as
+    /// [`ExponentialBackoff::with_max_retries()`].
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.inner = self.inner.with_max_retries(max_retries);
+        self
+    }
+
+    /// Restarts the strategy from its base delay with a fresh retry count.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl<R: JitterSource> Iterator for JitteredExponentialBackoff<R> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let nominal = self.inner.next()?;
+        Some(self.source.next_duration(Duration::ZERO, nominal))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// # Panics
+///
+/// [`len()`](ExactSizeIterator::len) panics if no
+/// [`with_max_retries()`](JitteredExponentialBackoff::with_max_retries) limit
+/// is set, as the strategy is then infinite.
+#[cfg(feature = "jitter")]
+impl<R: JitterSource> ExactSizeIterator for JitteredExponentialBackoff<R> {}
+
+// --- Default Policy ---
+
+/// The base delay of the strategy returned by [`default_backoff()`].
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// The number of retries allowed by the strategy returned by [`default_backoff()`].
+pub const DEFAULT_MAX_RETRIES: usize = 5;
+
+/// The strategy returned by [`default_backoff()`].
+///
+/// This is [`ExponentialBackoff`] with full jitter applied when the `jitter`
+/// feature is enabled, and without it otherwise. The type is the same either
+/// way, so code that names it builds whichever features are enabled.
+#[derive(Debug, Clone)]
+pub struct DefaultBackoff {
+    #[cfg(feature = "jitter")]
+    inner: Jitter<ExponentialBackoff>,
+    #[cfg(not(feature = "jitter"))]
+    inner: ExponentialBackoff,
+}
+
+impl Iterator for DefaultBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Returns the crate's recommended general-purpose backoff strategy.
+///
+/// Exponential backoff starting at [`DEFAULT_BASE_DELAY`] (100ms), limited to
+/// [`DEFAULT_MAX_RETRIES`] (5) retries, with full jitter applied when the
+/// `jitter` feature is enabled.
+pub fn default_backoff() -> DefaultBackoff {
+    let strategy =
+        ExponentialBackoff::new(DEFAULT_BASE_DELAY).with_max_retries(DEFAULT_MAX_RETRIES);
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "jitter")] {
+            DefaultBackoff { inner: Jitter::new(strategy) }
+        } else {
+            DefaultBackoff { inner: strategy }
+        }
+    }
+}
+
+// --- Builder ---
+
+/// A single entry point for building any of the common strategies.
+///
+/// Pick a kind with [`fixed()`](BackoffBuilder::fixed),
+/// [`exponential()`](BackoffBuilder::exponential) or
+/// [`fibonacci()`](BackoffBuilder::fibonacci), add limits, and call
+/// [`build()`](BackoffBuilder::build) to get a boxed strategy. Without a kind,
+/// the builder produces exponential backoff from [`DEFAULT_BASE_DELAY`]. Use
+/// the concrete types directly for settings the builder doesn't cover.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::backoff::BackoffBuilder;
+/// use std::time::Duration;
+///
+/// let strategy = BackoffBuilder::new()
+///     .exponential(Duration::from_millis(100))
+///     .max_retries(5)
+///     .max_delay(Duration::from_millis(500))
+///     .build();
+/// assert_eq!(
+///     strategy.collect::<Vec<_>>(),
+///     [100, 200, 400, 500, 500].map(Duration::from_millis)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[must_use = "call `build()` to create the strategy"]
+pub struct BackoffBuilder {
+    kind: BackoffKind,
+    max_retries: Option<usize>,
+    max_delay: Option<Duration>,
+    #[cfg(feature = "jitter")]
+    jitter: bool,
+}
+
+/// The strategy a [`BackoffBuilder`] builds, with its base delay.
+#[derive(Debug, Clone, Copy)]
+enum BackoffKind {
+    Fixed(Duration),
+    Exponential(Duration),
+    Fibonacci(Duration),
+}
+
+impl BackoffBuilder {
+    /// Creates a builder for exponential backoff from [`DEFAULT_BASE_DELAY`],
+    /// with no limits.
+    pub const fn new() -> Self {
+        Self {
+            kind: BackoffKind::Exponential(DEFAULT_BASE_DELAY),
+            max_retries: None,
+            max_delay: None,
+            #[cfg(feature = "jitter")]
+            jitter: false,
+        }
+    }
+
+    /// Builds a [`FixedDelay`] strategy waiting `delay` between attempts.
+    pub const fn fixed(mut self, delay: Duration) -> Self {
+        self.kind = BackoffKind::Fixed(delay);
+        self
+    }
+
+    /// Builds an [`ExponentialBackoff`] strategy starting at `base_delay`.
+    pub const fn exponential(mut self, base_delay: Duration) -> Self {
+        self.kind = BackoffKind::Exponential(base_delay);
+        self
+    }
+
+    /// Builds a [`FibonacciBackoff`] strategy starting at `base_delay`.
+    pub const fn fibonacci(mut self, base_delay: Duration) -> Self {
+        self.kind = BackoffKind::Fibonacci(base_delay);
+        self
+    }
+
+    /// Limits the number of retries.
+    pub const fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Caps every delay at `max_delay`.
+    pub const fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Adds full [`Jitter`] to every delay, after the cap. It requires the
+    /// `jitter` feature flag.
+    #[cfg(feature = "jitter")]
+    pub const fn jitter(mut self) -> Self {
+        self.jitter = true;
+        self
+    }
+
+    /// Builds the configured strategy.
+    pub fn build(self) -> Box<dyn Backoff + Send> {
+        let strategy = match self.kind {
+            BackoffKind::Fixed(delay) => {
+                let delay = self
+                    .max_delay
+                    .map_or(delay, |max_delay| delay.min(max_delay));
+                let strategy = FixedDelay::new(delay);
+                match self.max_retries {
+                    Some(max_retries) => strategy.take(max_retries).boxed(),
+                    None => strategy.boxed(),
+                }
+            }
+            BackoffKind::Exponential(base_delay) => {
+                let mut strategy = ExponentialBackoff::new(base_delay);
+                if let Some(max_delay) = self.max_delay {
+                    strategy = strategy.with_max_delay(max_delay);
+                }
+                if let Some(max_retries) = self.max_retries {
+                    strategy = strategy.with_max_retries(max_retries);
+                }
+                strategy.boxed()
+            }
+            BackoffKind::Fibonacci(base_delay) => {
+                let mut strategy = FibonacciBackoff::new(base_delay);
+                if let Some(max_delay) = self.max_delay {
+                    strategy = strategy.with_max_delay(max_delay);
+                }
+                if let Some(max_retries) = self.max_retries {
+                    strategy = strategy.with_max_retries(max_retries);
+                }
+                strategy.boxed()
+            }
+        };
+
+        #[cfg(feature = "jitter")]
+        if self.jitter {
+            return Jitter::new(strategy).boxed();
+        }
+        strategy
+    }
+}
+
+impl Default for BackoffBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// --- Random Delay Strategy ---
+
+/// A backoff strategy that waits for a uniformly random duration in `[min, max]`.
+///
+/// Unlike wrapping [`FixedDelay`] in [`Jitter`], this keeps a lower bound on
+/// the delay, which makes it a good fit for polling loops that want spread
+/// without exponential growth. It requires the `jitter` feature flag.
+///
+/// This iterator is infinite unless limited (e.g., with `with_max_retries()`).
+#[cfg(feature = "jitter")]
+#[derive(Debug, Clone, Copy)]
+pub struct RandomDelay<R = ThreadRngSource> {
+    min: Duration,
+    max: Duration,
+    max_retries: Option<usize>,
+    attempt: usize,
+    source: R,
+}
+
+#[cfg(feature = "jitter")]
+impl RandomDelay {
+    /// Creates a new `RandomDelay` strategy.
+    ///
+    /// - `min`: The shortest delay that may be yielded.
+    /// - `max`: The longest delay that may be yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn new(min: Duration, max: Duration) -> Self {
+        assert!(min <= max, "RandomDelay: min must not be greater than max");
+        Self {
+            min,
+            max,
+            max_retries: None,
+            attempt: 0,
+            source: ThreadRngSource,
+        }
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl<R: JitterSource> RandomDelay<R> {
+    /// Sets an optional maximum number of retries.
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Draws the random delays from `source`, as
+    /// [`Jitter::with_jitter_source()`].
+    pub fn with_jitter_source<R2: JitterSource>(self, source: R2) -> RandomDelay<R2> {
+        RandomDelay {
+            min: self.min,
+            max: self.max,
+            max_retries: self.max_retries,
+            attempt: self.attempt,
+            source,
+        }
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl<R: JitterSource> Iterator for RandomDelay<R> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Check max retries limit
+        if let Some(max_retries) = self.max_retries {
+            if self.attempt >= max_retries {
+                return None;
+            }
+        }
+        self.attempt += 1;
+
+        Some(self.source.next_duration(self.min, self.max))
+    }
+}
+
+// --- Weighted Backoff ---
+
+/// A backoff strategy that picks each delay from one of several strategies,
+/// chosen at random by weight.
+///
+/// This is mainly useful for experimentation, e.g. taking 70% of delays from
+/// an exponential strategy and 30% from a fixed one. Only the chosen strategy
+/// is advanced at each step, so every strategy progresses through its own
+/// sequence at its own pace. A strategy that is exhausted when chosen is
+/// dropped and another one is picked; the weighted strategy ends when none
+/// with a non-zero weight remain. It requires the `jitter` feature flag.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::backoff::{Backoff, ExponentialBackoff, FixedDelay, WeightedBackoff};
+/// use std::time::Duration;
+///
+/// let strategy = WeightedBackoff::new(vec![
+///     (70, ExponentialBackoff::new(Duration::from_millis(100)).boxed()),
+///     (30, FixedDelay::new(Duration::from_millis(500)).boxed()),
+/// ])
+/// .take(10);
+/// ```
+#[cfg(feature = "jitter")]
+pub struct WeightedBackoff<R = SeededSource> {
+    strategies: Vec<(u32, Box<dyn Backoff + Send>)>,
+    source: R,
+}
+
+#[cfg(feature = "jitter")]
+impl WeightedBackoff {
+    /// Creates a new `WeightedBackoff` choosing among `strategies`, each
+    /// paired with its relative weight.
+    pub fn new(strategies: Vec<(u32, Box<dyn Backoff + Send>)>) -> Self {
+        Self {
+            strategies,
+            source: SeededSource::from_entropy(),
+        }
+    }
+
+    /// Seeds the random selection, making the sequence of choices reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.source = SeededSource::new(seed);
+        self
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl<R: JitterSource> WeightedBackoff<R> {
+    /// Draws the random choices from `source`, as
+    /// [`Jitter::with_jitter_source()`].
+    pub fn with_jitter_source<R2: JitterSource>(self, source: R2) -> WeightedBackoff<R2> {
+        WeightedBackoff {
+            strategies: self.strategies,
+            source,
+        }
+    }
+
+    /// Picks the index of a strategy at random by weight.
+    fn choose(&mut self) -> Option<usize> {
+        let total: u64 = self.strategies.iter().map(|(w, _)| u64::from(*w)).sum();
+        if total == 0 {
+            return None;
+        }
+        // `unit()` may return exactly 1, so keep the pick below `total`.
+        let mut pick = ((unit(&mut self.source) * total as f64) as u64).min(total - 1);
+        self.strategies.iter().position(|(weight, _)| {
+            let weight = u64::from(*weight);
+            if pick < weight {
+                return true;
+            }
+            pick -= weight;
+            false
+        })
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl<R> fmt::Debug for WeightedBackoff<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let weights: Vec<u32> = self.strategies.iter().map(|(w, _)| *w).collect();
+        f.debug_struct("WeightedBackoff")
+            .field("weights", &weights)
+            .finish()
+    }
+}
+
+#[cfg(feature = "jitter")]
+impl<R: JitterSource> Iterator for WeightedBackoff<R> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.choose()?;
+            match self.strategies[index].1.next() {
+                Some(delay) => return Some(delay),
+                // Drop the exhausted strategy and choose again.
+                None => {
+                    self.strategies.remove(index);
+                }
+            }
+        }
+    }
+}
+
+// --- Unit Tests (as required by persona) ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_delay() {
+        let mut strategy = FixedDelay::new(Duration::from_secs(1)).take(3);
+        assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
+        assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
+        assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
+        assert_eq!(strategy.next(), None);
+    }
+
+    #[test]
+    fn test_unit_constructors_match_duration_constructors() {
+        let collect =
+            |strategy: &mut dyn Iterator<Item = Duration>| strategy.take(5).collect::<Vec<_>>();
+        let ms = Duration::from_millis(250);
+        let s = Duration::from_secs(2);
+
+        assert_eq!(
+            collect(&mut FixedDelay::from_millis(250)),
+            collect(&mut FixedDelay::new(ms))
+        );
+        assert_eq!(
+            collect(&mut FixedDelay::from_secs(2)),
+            collect(&mut FixedDelay::new(s))
+        );
+        assert_eq!(
+            collect(&mut ExponentialBackoff::from_millis(250)),
+            collect(&mut ExponentialBackoff::new(ms))
+        );
+        assert_eq!(
+            collect(&mut ExponentialBackoff::from_secs(2)),
+            collect(&mut ExponentialBackoff::new(s))
+        );
+        assert_eq!(
+            collect(&mut FibonacciBackoff::from_millis(250)),
+            collect(&mut FibonacciBackoff::new(ms))
+        );
+        assert_eq!(
+            collect(&mut FibonacciBackoff::from_secs(2)),
+            collect(&mut FibonacciBackoff::new(s))
+        );
+    }
+
+    #[test]
+    fn test_to_fit_sums_to_total() {
+        for (total, retries) in [
+            (Duration::from_millis(3100), 5),
+            (Duration::from_secs(60), 10),
+        ] {
+            let strategy = ExponentialBackoff::to_fit(total, retries);
+            assert_eq!(strategy.len(), retries);
+            let sum: Duration = strategy.sum();
+            let error = sum.max(total) - sum.min(total);
+            assert!(error < Duration::from_micros(1), "{:?}", sum);
+        }
+
+        // 3.1s over 5 retries is exactly 100ms doubling
+        let strategy = ExponentialBackoff::to_fit(Duration::from_millis(3100), 5);
+        assert_eq!(
+            strategy.collect::<Vec<_>>(),
+            [100, 200, 400, 800, 1600].map(Duration::from_millis)
+        );
+
+        assert_eq!(
+            ExponentialBackoff::to_fit(Duration::from_secs(1), 0).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_inspect_observes_yielded_delays() {
+        let mut seen = Vec::new();
+        let delays: Vec<_> = ExponentialBackoff::new(Duration::from_millis(100))
+            .with_max_retries(3)
+            .inspect(|delay| seen.push(*delay))
+            .collect();
+
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+            ]
+        );
+        assert_eq!(seen, delays);
+    }
+
+    #[test]
+    fn test_take_while_total() {
+        let strategy = ExponentialBackoff::new(Duration::from_millis(100))
+            .take_while_total(Duration::from_millis(1000));
+        let delays: Vec<_> = strategy.collect();
+        // 100 + 200 + 400 = 700ms; adding 800ms would exceed the 1000ms cap.
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_take_while_total_allows_exact_cap() {
+        let mut strategy = ExponentialBackoff::new(Duration::from_millis(100))
+            .take_while_total(Duration::from_millis(700));
+        assert_eq!(strategy.by_ref().count(), 3);
+        assert_eq!(strategy.next(), None);
+    }
+
+    #[test]
+    fn test_fixed_delay_try_new_rejects_zero() {
+        assert_eq!(
+            FixedDelay::try_new(Duration::ZERO).unwrap_err(),
+            BackoffError::ZeroDelay
+        );
+        assert!(FixedDelay::try_new(Duration::from_millis(1)).is_ok());
+    }
+
+    #[test]
+    fn test_exponential_backoff_try_new_rejects_zero() {
+        assert_eq!(
+            ExponentialBackoff::try_new(Duration::ZERO).unwrap_err(),
+            BackoffError::ZeroDelay
+        );
+    }
+
+    #[test]
+    fn test_exponential_backoff_zero_base_is_kept() {
+        let mut strategy = ExponentialBackoff::new(Duration::ZERO).with_max_retries(3);
+        assert_eq!(strategy.next(), Some(Duration::ZERO));
+        assert_eq!(strategy.next(), Some(Duration::ZERO));
+        assert_eq!(strategy.next(), Some(Duration::ZERO));
+        assert_eq!(strategy.next(), None);
+    }
+
+    #[test]
+    fn test_fixed_delay_peek_next() {
+        let strategy = FixedDelay::new(Duration::from_secs(1));
+        assert_eq!(strategy.peek_next(), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_exponential_backoff_peek_next() {
+        let mut strategy = ExponentialBackoff::new(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(300))
+            .with_max_retries(3);
+        for _ in 0..3 {
+            let peeked = strategy.peek_next();
+            assert_eq!(strategy.peek_next(), peeked); // Peeking doesn't advance
+            assert_eq!(strategy.next(), peeked);
+        }
+        assert_eq!(strategy.peek_next(), None);
+        assert_eq!(strategy.next(), None);
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let mut strategy = ExponentialBackoff::new(Duration::from_millis(100)).take(4);
+        assert_eq!(strategy.next(), Some(Duration::from_millis(100)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(200)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(400)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(800)));
+        assert_eq!(strategy.next(), None);
+    }
+
+    #[test]
+    fn test_exponential_backoff_integer_multiplier() {
+        let mut strategy =
+            ExponentialBackoff::new(Duration::from_millis(100)).with_integer_multiplier(3);
+        assert_eq!(strategy.next(), Some(Duration::from_millis(100)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(300)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(900)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(2700)));
+
+        // Sub-millisecond bases are multiplied exactly too
+        let mut strategy =
+            ExponentialBackoff::new(Duration::from_nanos(333_333)).with_integer_multiplier(3);
+        strategy.next();
+        assert_eq!(strategy.next(), Some(Duration::from_nanos(999_999)));
+    }
+
+    #[test]
+    fn test_exponential_backoff_fractional_multiplier() {
+        let mut strategy = ExponentialBackoff::new(Duration::from_millis(100)).with_multiplier(1.5);
+        assert_eq!(strategy.next(), Some(Duration::from_millis(100)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(150)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(225)));
+    }
+
+    #[test]
     fn test_exponential_backoff_with_max_delay() {
         let mut strategy = ExponentialBackoff::new(Duration::from_millis(100))
             .with_max_delay(Duration::from_millis(300))
@@ -313,6 +2112,32 @@ mod tests {
         assert_eq!(strategy.next(), None);
     }
 
+    #[test]
+    fn test_truncated_exponential_backoff() {
+        let cap = Duration::from_secs(10);
+        let mut strategy = ExponentialBackoff::truncated(Duration::from_secs(1), cap);
+        let delays: Vec<Duration> = strategy.by_ref().take(5).collect();
+        assert_eq!(
+            delays,
+            [1, 2, 4, 8, 10].map(Duration::from_secs).to_vec() // Capped
+        );
+        for _ in 0..1000 {
+            assert_eq!(strategy.next(), Some(cap));
+        }
+        // The internal delay stopped growing once it passed the cap
+        assert_eq!(strategy.current, Duration::from_secs(16));
+    }
+
+    #[test]
+    fn test_exponential_backoff_reset() {
+        let mut strategy = ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(2);
+        assert_eq!(strategy.next(), Some(Duration::from_millis(100)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(200)));
+        assert_eq!(strategy.next(), None);
+        strategy.reset();
+        assert_eq!(strategy.next(), Some(Duration::from_millis(100)));
+    }
+
     #[test]
     fn test_exponential_backoff_with_max_retries() {
         let mut strategy = ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(2);
@@ -321,6 +2146,61 @@ mod tests {
         assert_eq!(strategy.next(), None); // Limit reached
     }
 
+    #[test]
+    fn test_default_backoff() {
+        let delays: Vec<Duration> = default_backoff().collect();
+        assert_eq!(delays.len(), DEFAULT_MAX_RETRIES);
+        // Jitter (if enabled) only ever shortens the exponential schedule
+        for (i, delay) in delays.into_iter().enumerate() {
+            assert!(delay <= DEFAULT_BASE_DELAY * 2u32.pow(i as u32));
+        }
+    }
+
+    #[test]
+    fn test_from_schedule() {
+        let schedule = vec![
+            Duration::from_millis(250),
+            Duration::from_secs(2),
+            Duration::from_millis(10),
+        ];
+        let mut strategy = FromSchedule::new(schedule.clone());
+        assert_eq!(strategy.peek_next(), Some(schedule[0]));
+        assert_eq!(strategy.by_ref().collect::<Vec<_>>(), schedule);
+        assert_eq!(strategy.next(), None);
+        assert_eq!(strategy.peek_next(), None);
+    }
+
+    #[test]
+    fn test_from_schedule_empty() {
+        let mut strategy = FromSchedule::new([]);
+        assert_eq!(strategy.next(), None);
+    }
+
+    #[test]
+    fn test_adaptive_poll_tracks_health() {
+        let secs = Duration::from_secs;
+        let mut pacing = AdaptivePoll::new(secs(1), secs(8));
+
+        // Healthy and unchanged: slow down until the cap
+        let steady = (0..5).map(|_| pacing.observe(false)).collect::<Vec<_>>();
+        assert_eq!(steady, [1, 2, 4, 8, 8].map(secs));
+
+        // The resource went unhealthy: poll quickly, then slow down again
+        assert_eq!(pacing.observe(true), secs(1));
+        assert_eq!(pacing.observe(false), secs(2));
+        assert_eq!(pacing.peek_next(), secs(4));
+
+        // Back to healthy: another change resets it
+        assert_eq!(pacing.observe(true), secs(1));
+        assert_eq!(pacing.next(), Some(secs(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "min_interval must not exceed max_interval")]
+    fn test_adaptive_poll_rejects_inverted_bounds() {
+        AdaptivePoll::new(Duration::from_secs(2), Duration::from_secs(1));
+    }
+
     #[test]
     fn test_fibonacci_backoff() {
         let mut strategy = FibonacciBackoff::new(Duration::from_secs(1)).take(6);
@@ -333,6 +2213,67 @@ mod tests {
         assert_eq!(strategy.next(), None);
     }
 
+    #[test]
+    fn test_fibonacci_backoff_peek_next() {
+        let mut strategy = FibonacciBackoff::new(Duration::from_secs(1)).with_max_retries(4);
+        for _ in 0..4 {
+            let peeked = strategy.peek_next();
+            assert_eq!(strategy.next(), peeked);
+        }
+        assert_eq!(strategy.peek_next(), None);
+    }
+
+    #[test]
+    fn test_fibonacci_backoff_saturates() {
+        let mut strategy = FibonacciBackoff::new(Duration::from_secs(1));
+        let mut previous = Duration::ZERO;
+        for _ in 0..500 {
+            let delay = strategy.next().unwrap();
+            assert!(delay >= previous); // Never wraps back around
+            previous = delay;
+        }
+        assert_eq!(previous, Duration::MAX);
+        assert_eq!(strategy.next(), Some(Duration::MAX));
+    }
+
+    #[test]
+    fn test_fibonacci_backoff_saturates_with_max_delay() {
+        let cap = Duration::from_secs(60);
+        let mut strategy = FibonacciBackoff::new(Duration::from_secs(1)).with_max_delay(cap);
+        for _ in 0..500 {
+            assert!(strategy.next().unwrap() <= cap);
+        }
+        assert_eq!(strategy.next(), Some(cap));
+    }
+
+    #[test]
+    fn test_fibonacci_backoff_stops_advancing_at_cap() {
+        let cap = Duration::from_secs(60);
+        let mut strategy = FibonacciBackoff::new(Duration::from_secs(1)).with_max_delay(cap);
+        // 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, then capped at 60
+        let delays: Vec<_> = strategy.by_ref().take(11).collect();
+        assert_eq!(delays[9], Duration::from_secs(55));
+        assert_eq!(delays[10], cap);
+        let state = (strategy.current, strategy.next);
+
+        for _ in 0..1000 {
+            assert_eq!(strategy.next(), Some(cap));
+        }
+        // The internal sequence stopped at the first value past the cap.
+        assert_eq!((strategy.current, strategy.next), state);
+        assert_eq!(strategy.current, Duration::from_secs(89));
+    }
+
+    #[test]
+    fn test_fibonacci_backoff_reset() {
+        let mut strategy = FibonacciBackoff::new(Duration::from_secs(1));
+        strategy.by_ref().take(5).for_each(drop);
+        strategy.reset();
+        assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
+        assert_eq!(strategy.next(), Some(Duration::from_secs(1)));
+        assert_eq!(strategy.next(), Some(Duration::from_secs(2)));
+    }
+
     #[test]
     fn test_fibonacci_backoff_with_max_retries() {
         let mut strategy = FibonacciBackoff::new(Duration::from_secs(1)).with_max_retries(3);
@@ -342,6 +2283,222 @@ mod tests {
         assert_eq!(strategy.next(), None); // Limit reached
     }
 
+    #[test]
+    fn test_exponential_backoff_with_first_delay() {
+        let mut strategy = ExponentialBackoff::new(Duration::from_millis(100))
+            .with_first_delay(Duration::from_secs(1))
+            .with_max_delay(Duration::from_millis(300));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(300))); // Capped
+        assert_eq!(strategy.next(), Some(Duration::from_millis(200)));
+        assert_eq!(strategy.next(), Some(Duration::from_millis(300)));
+
+        strategy.reset();
+        assert_eq!(strategy.peek_next(), Some(Duration::from_millis(300)));
+    }
+
+    #[test]
+    fn test_builder_kinds() {
+        let build = |builder: BackoffBuilder| builder.max_retries(5).build().collect::<Vec<_>>();
+        let base = Duration::from_millis(100);
+
+        assert_eq!(
+            build(BackoffBuilder::new().fixed(base)),
+            [100, 100, 100, 100, 100].map(Duration::from_millis)
+        );
+        assert_eq!(
+            build(BackoffBuilder::new().exponential(base)),
+            [100, 200, 400, 800, 1600].map(Duration::from_millis)
+        );
+        assert_eq!(
+            build(BackoffBuilder::new().fibonacci(base)),
+            [100, 100, 200, 300, 500].map(Duration::from_millis)
+        );
+        assert_eq!(
+            build(
+                BackoffBuilder::new()
+                    .fixed(base)
+                    .max_delay(Duration::from_millis(50))
+            ),
+            [50, 50, 50, 50, 50].map(Duration::from_millis)
+        );
+        // Without a kind, exponential from the default base delay
+        assert_eq!(
+            build(BackoffBuilder::new()),
+            build(BackoffBuilder::new().exponential(base))
+        );
+    }
+
+    /// A jitter source that always returns the same value.
+    #[cfg(feature = "jitter")]
+    #[derive(Clone, Copy)]
+    struct Constant(f64);
+
+    #[cfg(feature = "jitter")]
+    impl JitterSource for Constant {
+        fn next_f64(&mut self) -> f64 {
+            self.0
+        }
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_jitter_ranges_include_max() {
+        let ms = Duration::from_millis;
+
+        // A source at the top of its range yields the maximum delay
+        let mut full = Jitter::new(FixedDelay::new(ms(1000))).with_jitter_source(Constant(1.0));
+        assert_eq!(full.next(), Some(ms(1000)));
+        let mut random = RandomDelay::new(ms(1000), ms(3000)).with_jitter_source(Constant(1.0));
+        assert_eq!(random.next(), Some(ms(3000)));
+
+        // The rand-backed sources draw whole nanoseconds from both ends
+        let ns = Duration::from_nanos;
+        let mut source = SeededSource::new(7);
+        let drawn: std::collections::HashSet<_> = (0..200)
+            .map(|_| source.next_duration(ns(1), ns(3)))
+            .collect();
+        assert_eq!(drawn, [ns(1), ns(2), ns(3)].into_iter().collect());
+        assert_eq!(ThreadRngSource.next_duration(ns(5), ns(5)), ns(5));
+        assert_eq!(ThreadRngSource.next_duration(ns(5), ns(1)), ns(5));
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_jitter_sources_are_deterministic() {
+        let ms = Duration::from_millis;
+
+        let mut full = Jitter::new(FixedDelay::new(ms(1000))).with_jitter_source(Constant(0.25));
+        assert_eq!(full.next(), Some(ms(250)));
+        let mut bounded =
+            Jitter::bounded(FixedDelay::new(ms(1000)), 0.5, 1.5).with_jitter_source(Constant(0.5));
+        assert_eq!(bounded.next(), Some(ms(1000)));
+
+        let exponential = ExponentialBackoff::new(ms(100))
+            .jittered()
+            .with_jitter_source(Constant(0.5))
+            .with_max_retries(3);
+        assert_eq!(exponential.collect::<Vec<_>>(), [50, 100, 200].map(ms));
+
+        let mut random = RandomDelay::new(ms(1000), ms(3000)).with_jitter_source(Constant(0.5));
+        assert_eq!(random.next(), Some(ms(2000)));
+
+        // Out-of-range values are clamped
+        let mut clamped = RandomDelay::new(ms(1000), ms(3000)).with_jitter_source(Constant(7.0));
+        assert_eq!(clamped.next(), Some(ms(3000)));
+        let mut nan = RandomDelay::new(ms(1000), ms(3000)).with_jitter_source(Constant(f64::NAN));
+        assert_eq!(nan.next(), Some(ms(1000)));
+
+        let weighted = || {
+            WeightedBackoff::new(vec![
+                (1, FixedDelay::new(ms(10)).boxed()),
+                (1, FixedDelay::new(ms(20)).boxed()),
+            ])
+        };
+        let mut first = weighted().with_jitter_source(Constant(0.0));
+        assert_eq!(first.next(), Some(ms(10)));
+        let mut last = weighted().with_jitter_source(Constant(1.0));
+        assert_eq!(last.next(), Some(ms(20)));
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_seeded_source_repeats() {
+        let strategy = |seed| {
+            Jitter::new(ExponentialBackoff::new(Duration::from_millis(100)))
+                .with_jitter_source(SeededSource::new(seed))
+                .take(8)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(strategy(7), strategy(7));
+        assert_ne!(strategy(7), strategy(8));
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_jitter_with_max_retries() {
+        let strategy = Jitter::new(FixedDelay::new(Duration::from_millis(100))).with_max_retries(3);
+        assert_eq!(strategy.size_hint(), (3, Some(3)));
+        assert_eq!(strategy.count(), 3);
+
+        // The inner strategy's own, lower limit still applies
+        let strategy = Jitter::proportional(NoDelay::with_max_retries(2), 0.5).with_max_retries(5);
+        assert_eq!(strategy.size_hint(), (2, Some(2)));
+        assert_eq!(strategy.count(), 2);
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_jittered_exponential_respects_limits() {
+        let max_delay = Duration::from_millis(400);
+        let strategy = ExponentialBackoff::new(Duration::from_millis(100))
+            .jittered()
+            .with_max_delay(max_delay)
+            .with_max_retries(10);
+        assert_eq!(strategy.len(), 10);
+
+        let delays = strategy.collect::<Vec<_>>();
+        assert_eq!(delays.len(), 10);
+        // Each delay is within the exponential delay it replaces.
+        for (i, delay) in delays.into_iter().enumerate() {
+            let nominal = (Duration::from_millis(100) * 2u32.pow(i as u32)).min(max_delay);
+            assert!(delay <= nominal);
+        }
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_builder_jitter() {
+        let max_delay = Duration::from_millis(300);
+        let delays = BackoffBuilder::new()
+            .exponential(Duration::from_millis(100))
+            .max_retries(10)
+            .max_delay(max_delay)
+            .jitter()
+            .build()
+            .collect::<Vec<_>>();
+        assert_eq!(delays.len(), 10);
+        assert!(delays.iter().all(|&delay| delay <= max_delay));
+    }
+
+    #[test]
+    fn test_map_delay_scales_exponential() {
+        let strategy = ExponentialBackoff::new(Duration::from_millis(100))
+            .with_max_retries(4)
+            .map_delay(|delay| delay * 2);
+        assert_eq!(strategy.len(), 4);
+        assert_eq!(
+            strategy.collect::<Vec<_>>(),
+            [200, 400, 800, 1600].map(Duration::from_millis)
+        );
+    }
+
+    #[test]
+    fn test_finite_strategies_report_len() {
+        let mut exponential =
+            ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(5);
+        assert_eq!(exponential.len(), 5);
+        exponential.next();
+        exponential.next();
+        assert_eq!(exponential.len(), 3);
+        assert_eq!(exponential.by_ref().count(), 3);
+        assert_eq!(exponential.len(), 0);
+
+        let fibonacci = FibonacciBackoff::new(Duration::from_secs(1)).with_max_retries(5);
+        assert_eq!(fibonacci.len(), 5);
+        assert_eq!(fibonacci.take(2).len(), 2);
+    }
+
+    #[test]
+    fn test_infinite_strategies_have_no_len() {
+        let exponential = ExponentialBackoff::new(Duration::from_millis(100));
+        assert_eq!(exponential.size_hint(), (usize::MAX, None));
+        assert!(std::panic::catch_unwind(|| exponential.len()).is_err());
+
+        // `.take(n)` on an infinite strategy still knows its length.
+        let fixed = FixedDelay::new(Duration::from_secs(1)).take(5);
+        assert_eq!(fixed.size_hint(), (5, Some(5)));
+    }
+
     #[cfg(feature = "jitter")]
     #[test]
     fn test_jitter_wrapper() {
@@ -353,4 +2510,161 @@ mod tests {
         }
         assert_eq!(jitter.next(), None);
     }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_jitter_preserves_sub_millisecond_delays() {
+        let base = Duration::from_micros(500);
+        let delays: Vec<_> = Jitter::new(FixedDelay::new(base)).take(100).collect();
+        assert!(delays.iter().all(|d| *d <= base));
+        // Truncating to milliseconds would make every delay zero.
+        let zeros = delays.iter().filter(|d| d.is_zero()).count();
+        assert!(zeros < 10, "{zeros} of 100 jittered delays were zero");
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_jitter_observer_reports_nominal_and_jittered() {
+        use std::sync::Mutex;
+
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let observed_clone = observed.clone();
+        let strategy = Jitter::new(ExponentialBackoff::new(Duration::from_millis(100)))
+            .with_observer(move |nominal, jittered| {
+                observed_clone.lock().unwrap().push((nominal, jittered));
+            })
+            .take(3);
+        let yielded: Vec<_> = strategy.collect();
+
+        let observed = observed.lock().unwrap();
+        let nominal: Vec<_> = observed.iter().map(|(n, _)| *n).collect();
+        let jittered: Vec<_> = observed.iter().map(|(_, j)| *j).collect();
+        assert_eq!(
+            nominal,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+            ]
+        );
+        assert_eq!(jittered, yielded);
+        assert!(observed.iter().all(|(n, j)| j <= n));
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_proportional_jitter() {
+        let base = Duration::from_secs(1);
+        let mut jitter = Jitter::proportional(FixedDelay::new(base), 0.2).take(100);
+        for _ in 0..100 {
+            let duration = jitter.next().unwrap();
+            assert!(duration >= base.mul_f64(0.8) && duration <= base.mul_f64(1.2));
+        }
+        assert_eq!(jitter.next(), None);
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    #[should_panic]
+    fn test_proportional_jitter_rejects_invalid_fraction() {
+        let _ = Jitter::proportional(FixedDelay::new(Duration::from_secs(1)), 1.5);
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_bounded_jitter() {
+        let base = Duration::from_secs(1);
+        for (min_factor, max_factor) in [(0.9, 1.3), (0.5, 1.0), (0.0, 2.0), (1.0, 1.0)] {
+            let mut jitter = Jitter::bounded(FixedDelay::new(base), min_factor, max_factor);
+            for _ in 0..100 {
+                let duration = jitter.next().unwrap();
+                assert!(
+                    duration >= base.mul_f64(min_factor) && duration <= base.mul_f64(max_factor),
+                    "{:?} outside {}..={}",
+                    duration,
+                    min_factor,
+                    max_factor
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    #[should_panic(expected = "min_factor must not be greater than max_factor")]
+    fn test_bounded_jitter_rejects_inverted_factors() {
+        let _ = Jitter::bounded(FixedDelay::new(Duration::from_secs(1)), 1.3, 0.9);
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_weighted_backoff_distribution() {
+        let fast = Duration::from_millis(1);
+        let slow = Duration::from_millis(2);
+        let strategy = WeightedBackoff::new(vec![
+            (70, FixedDelay::new(fast).boxed()),
+            (30, FixedDelay::new(slow).boxed()),
+        ])
+        .with_seed(42);
+
+        let delays: Vec<_> = strategy.take(10_000).collect();
+        let fast_count = delays.iter().filter(|d| **d == fast).count();
+        assert!((6_700..=7_300).contains(&fast_count), "{}", fast_count);
+
+        // The same seed makes the same choices
+        let again = WeightedBackoff::new(vec![
+            (70, FixedDelay::new(fast).boxed()),
+            (30, FixedDelay::new(slow).boxed()),
+        ])
+        .with_seed(42);
+        assert_eq!(again.take(10_000).collect::<Vec<_>>(), delays);
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_weighted_backoff_advances_only_chosen_strategy() {
+        let strategy = WeightedBackoff::new(vec![
+            (
+                1,
+                ExponentialBackoff::new(Duration::from_millis(100))
+                    .take(3)
+                    .boxed(),
+            ),
+            (0, FixedDelay::new(Duration::from_secs(1)).boxed()),
+        ]);
+
+        // The zero-weight strategy is never chosen, and the exponential one
+        // yields its own sequence before the whole strategy ends.
+        assert_eq!(
+            strategy.collect::<Vec<_>>(),
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+            ]
+        );
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_random_delay_within_bounds() {
+        let min = Duration::from_millis(100);
+        let max = Duration::from_millis(200);
+        let mut strategy = RandomDelay::new(min, max).take(100);
+        for _ in 0..100 {
+            let duration = strategy.next().unwrap();
+            assert!(duration >= min && duration <= max);
+        }
+        assert_eq!(strategy.next(), None);
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_random_delay_with_max_retries() {
+        let min = Duration::from_millis(10);
+        let mut strategy = RandomDelay::new(min, min).with_max_retries(2);
+        assert_eq!(strategy.next(), Some(min));
+        assert_eq!(strategy.next(), Some(min));
+        assert_eq!(strategy.next(), None); // Limit reached
+    }
 }
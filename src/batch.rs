@@ -0,0 +1,98 @@
+// Author: Jacques Murray
+
+//! Retrying a batch of independent operations with one shared strategy.
+
+use crate::backoff::Backoff;
+use crate::Retry;
+use std::fmt::Display;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::task::Poll;
+
+/// Retries every operation in `operations` concurrently, each with its own
+/// clone of `strategy`, and collects their results in order.
+///
+/// Every operation gets a fresh backoff, so one that keeps failing does not
+/// slow down the others, and the whole batch takes about as long as its
+/// slowest member. Errors are retried unconditionally, as with
+/// [`Retry::new()`]; build the `Retry`s yourself if you need a condition or
+/// other options.
+///
+/// The operations are driven concurrently within the calling task rather than
+/// spawned, so this works on any runtime and does not require the operations
+/// to be `Send` or `'static`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_retry::{retry_all, backoff::ExponentialBackoff};
+/// use std::time::Duration;
+///
+/// # #[derive(Debug, Clone)]
+/// # struct MyError;
+/// # impl std::fmt::Display for MyError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+/// # }
+/// # async fn fetch(_: &str) -> Result<String, MyError> { Ok(String::new()) }
+/// # async fn example() {
+/// let urls = ["https://a.example", "https://b.example", "https://c.example"];
+///
+/// let results = retry_all(
+///     ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(3),
+///     urls.iter().map(|url| move || fetch(url)),
+/// )
+/// .await;
+///
+/// for (url, result) in urls.iter().zip(results) {
+///     match result {
+///         Ok(body) => println!("{url}: {} bytes", body.len()),
+///         Err(e) => eprintln!("{url}: gave up: {e}"),
+///     }
+/// }
+/// # }
+/// ```
+pub async fn retry_all<S, I, O, F, T, E>(strategy: S, operations: I) -> Vec<Result<T, E>>
+where
+    S: Backoff + Clone,
+    I: IntoIterator<Item = O>,
+    O: FnMut() -> F,
+    F: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    let runs = operations
+        .into_iter()
+        .map(|operation| {
+            let retry = Retry::new(strategy.clone(), operation);
+            Box::pin(async move { retry.execute().await.0 })
+        })
+        .collect();
+    join_all(runs).await
+}
+
+/// Polls every future to completion concurrently, keeping their order.
+///
+/// Every unfinished future is polled again on each wake, which is linear in
+/// the size of the batch but avoids per-future wakers, and with them any need
+/// for a runtime or a `futures` dependency.
+async fn join_all<F: Future>(mut futures: Vec<Pin<Box<F>>>) -> Vec<F::Output> {
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+    poll_fn(|cx| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if output.is_some() {
+                continue;
+            }
+            match future.as_mut().poll(cx) {
+                Poll::Ready(value) => *output = Some(value),
+                Poll::Pending => pending = true,
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+    outputs.into_iter().map(Option::unwrap).collect()
+}
@@ -0,0 +1,68 @@
+// Author: Jacques Murray
+
+//! Operations that are told how much of the time budget is left.
+
+use crate::backoff::Backoff;
+use crate::circuit::CircuitGuard;
+use crate::clock::Clock;
+use crate::condition::RetryCondition;
+use crate::Retry;
+use std::fmt::Display;
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::time::Duration;
+
+/// An operation that is handed the time left in the maximum duration on
+/// every attempt.
+///
+/// Created by [`Retry::with_budget()`].
+#[derive(Debug, Clone)]
+pub struct Budgeted<Op> {
+    pub(crate) operation: Op,
+}
+
+/// The core retry logic, implemented via `IntoFuture` for operations that
+/// take the remaining budget.
+impl<S, Op, C, K, B, F, T, E> IntoFuture for Retry<S, Budgeted<Op>, C, K, B>
+where
+    S: Backoff + Send + 'static,
+    K: Clock + Clone + Send + Sync + 'static,
+    B: CircuitGuard<E> + Send + 'static,
+    Op: FnMut(Duration) -> F + Send + 'static,
+    C: RetryCondition<E> + Send + 'static,
+    F: Future<Output = Result<T, E>> + Send,
+    E: Display + Send,
+    T: Send,
+{
+    type Output = Result<T, E>;
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> <Retry<S, Budgeted<Op>, C, K, B> as IntoFuture>::IntoFuture {
+        Box::pin(async move {
+            let Retry {
+                mut strategy,
+                operation: Budgeted { mut operation },
+                mut config,
+            } = self;
+            let clock = config.clock.clone();
+            let max_duration = config.options.max_duration.unwrap_or(Duration::MAX);
+
+            // The budget starts on the first attempt of this run.
+            let mut start = None;
+            config
+                .execute(
+                    &mut strategy,
+                    &mut || {
+                        let now = clock.now();
+                        let elapsed = now.saturating_duration_since(*start.get_or_insert(now));
+                        operation(max_duration.saturating_sub(elapsed))
+                    },
+                    &mut |_: &T| true,
+                )
+                .await
+                .0
+        })
+    }
+}
@@ -0,0 +1,256 @@
+// Author: Jacques Murray
+
+//! A circuit breaker for failing fast when a dependency is known to be down.
+//!
+//! A [`CircuitBreaker`] is shared (via [`Arc`]) between every `Retry` that
+//! calls the same dependency. It moves between three states:
+//!
+//! - **Closed**: Requests flow normally. Consecutive failures are counted, and
+//!   reaching the failure threshold opens the circuit.
+//! - **Open**: Requests are rejected immediately with [`CircuitOpenError`]
+//!   until the reset timeout has passed.
+//! - **Half-open**: After the reset timeout, requests are let through as
+//!   trials. A success closes the circuit; a failure opens it again.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use async_retry::{Retry, backoff::FixedDelay, circuit::{CircuitBreaker, CircuitOpenError}};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # #[derive(Debug)]
+//! # enum MyError { Network, CircuitOpen }
+//! # impl std::fmt::Display for MyError {
+//! #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+//! # }
+//! impl From<CircuitOpenError> for MyError {
+//!     fn from(_: CircuitOpenError) -> Self {
+//!         MyError::CircuitOpen
+//!     }
+//! }
+//!
+//! # async fn call_dependency() -> Result<(), MyError> { Ok(()) }
+//! # async fn example() {
+//! // Open after 5 consecutive failures, try again after 30 seconds
+//! let breaker = Arc::new(CircuitBreaker::new(5, Duration::from_secs(30)));
+//!
+//! let result = Retry::new(
+//!     FixedDelay::new(Duration::from_millis(100)).take(3),
+//!     move || async move { call_dependency().await }
+//! )
+//! .with_circuit_breaker(breaker.clone())
+//! .await;
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Requests are rejected until the reset timeout passes.
+    Open,
+    /// Trial requests are allowed to probe whether the dependency recovered.
+    HalfOpen,
+}
+
+/// The error produced when a request is rejected by an open circuit.
+///
+/// Your operation's error type must implement `From<CircuitOpenError>` to be
+/// used with [`Retry::with_circuit_breaker()`](crate::Retry::with_circuit_breaker).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitOpenError;
+
+impl fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "circuit breaker is open")
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+/// A thread-safe circuit breaker shared across retry operations.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    inner: Mutex<CircuitInner>,
+}
+
+#[derive(Debug)]
+struct CircuitInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Creates a new, closed `CircuitBreaker`.
+    ///
+    /// - `failure_threshold`: Consecutive failures needed to open the circuit.
+    /// - `reset_timeout`: How long the circuit stays open before allowing a trial.
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold,
+            reset_timeout,
+            inner: Mutex::new(CircuitInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// Returns the current state, moving from open to half-open if the reset
+    /// timeout has passed.
+    pub fn state(&self) -> CircuitState {
+        let mut inner = self.inner.lock().unwrap();
+        self.refresh(&mut inner);
+        inner.state
+    }
+
+    /// Returns `Ok(())` if a request may proceed, or [`CircuitOpenError`] if
+    /// the circuit is open.
+    pub fn check(&self) -> Result<(), CircuitOpenError> {
+        match self.state() {
+            CircuitState::Open => Err(CircuitOpenError),
+            CircuitState::Closed | CircuitState::HalfOpen => Ok(()),
+        }
+    }
+
+    /// Records a successful request, closing the circuit.
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    /// Records a failed request, opening the circuit if the failure threshold
+    /// is reached or if a half-open trial failed.
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        self.refresh(&mut inner);
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let should_open = match inner.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => inner.consecutive_failures >= self.failure_threshold,
+            CircuitState::Open => false,
+        };
+        if should_open {
+            #[cfg(feature = "logging")]
+            log::warn!(
+                "Circuit breaker opened after {} consecutive failures.",
+                inner.consecutive_failures
+            );
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    /// Moves an open circuit to half-open once the reset timeout has passed.
+    fn refresh(&self, inner: &mut CircuitInner) {
+        if let (CircuitState::Open, Some(opened_at)) = (inner.state, inner.opened_at) {
+            if opened_at.elapsed() >= self.reset_timeout {
+                inner.state = CircuitState::HalfOpen;
+            }
+        }
+    }
+}
+
+/// Connects a `Retry` loop to an optional circuit breaker.
+///
+/// This is implemented for [`NoCircuitBreaker`] (the default) and for
+/// `Arc<CircuitBreaker>` when the error type implements
+/// `From<CircuitOpenError>`. You don't normally need to use it directly.
+pub trait CircuitGuard<E> {
+    /// Called before every attempt. Returning an error stops the retry loop.
+    fn before_attempt(&self) -> Result<(), E>;
+
+    /// Called after every attempt with whether it succeeded.
+    fn record(&self, success: bool);
+}
+
+/// The default [`CircuitGuard`], which never rejects requests.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoCircuitBreaker;
+
+impl<E> CircuitGuard<E> for NoCircuitBreaker {
+    fn before_attempt(&self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn record(&self, _success: bool) {}
+}
+
+impl<E> CircuitGuard<E> for Arc<CircuitBreaker>
+where
+    E: From<CircuitOpenError>,
+{
+    fn before_attempt(&self) -> Result<(), E> {
+        self.check().map_err(E::from)
+    }
+
+    fn record(&self, success: bool) {
+        if success {
+            self.record_success();
+        } else {
+            self.record_failure();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(breaker.check(), Err(CircuitOpenError));
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_open_to_half_open_to_closed() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(10));
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert_eq!(breaker.check(), Ok(()));
+
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_failure_reopens() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(10));
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+}
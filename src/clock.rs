@@ -0,0 +1,142 @@
+// Author: Jacques Murray
+
+//! Time sources for the retry loop.
+//!
+//! The retry loop reads the current time (to enforce `max_duration`) and
+//! sleeps between attempts through a [`Clock`]. By default this is
+//! [`SystemClock`], which uses [`Instant::now`] and the runtime-agnostic
+//! sleep selected by the timer feature flags.
+//!
+//! With the `test-util` feature, [`TestClock`] provides a virtual clock whose
+//! sleeps complete immediately, so timing logic can be tested without waiting
+//! on the wall clock.
+//!
+//! [`SleepFn`] keeps another clock's notion of time but sleeps with a
+//! user-supplied function, for runtimes the timer features don't cover.
+
+use std::fmt;
+use std::future::Future;
+#[cfg(feature = "test-util")]
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of time for the retry loop.
+pub trait Clock {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+
+    /// Asynchronously waits for `duration` to pass on this clock.
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+/// The default clock, backed by [`Instant::now`] and the runtime's timer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        crate::sleep::sleep(duration)
+    }
+}
+
+// --- Custom Sleep ---
+
+/// A clock that reads time from another clock but sleeps with a function.
+///
+/// Created by [`Retry::with_sleep_fn()`](crate::Retry::with_sleep_fn).
+#[derive(Clone)]
+pub struct SleepFn<K, F> {
+    clock: K,
+    sleep: F,
+}
+
+impl<K, F> SleepFn<K, F> {
+    /// Creates a clock that reads time from `clock` and sleeps by awaiting
+    /// `sleep(duration)`.
+    pub fn new(clock: K, sleep: F) -> Self {
+        Self { clock, sleep }
+    }
+}
+
+impl<K: fmt::Debug, F> fmt::Debug for SleepFn<K, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SleepFn")
+            .field("clock", &self.clock)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, F, Fut> Clock for SleepFn<K, F>
+where
+    K: Clock,
+    F: Fn(Duration) -> Fut,
+    Fut: Future<Output = ()> + Send,
+{
+    fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        (self.sleep)(duration)
+    }
+}
+
+// --- Test Clock ---
+
+/// A virtual clock for deterministic tests.
+///
+/// Sleeping on a `TestClock` advances its time by the requested duration
+/// and completes immediately. Clones share the same underlying time, so
+/// a test can keep a handle and inspect [`elapsed()`](TestClock::elapsed)
+/// after the retry finishes. It requires the `test-util` feature flag.
+#[cfg(feature = "test-util")]
+#[derive(Debug, Clone)]
+pub struct TestClock {
+    start: Instant,
+    offset: Arc<Mutex<Duration>>,
+}
+
+#[cfg(feature = "test-util")]
+impl TestClock {
+    /// Creates a new `TestClock` starting at the current instant.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            offset: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Advances the clock by `duration` without sleeping.
+    pub fn advance(&self, duration: Duration) {
+        let mut offset = self.offset.lock().unwrap();
+        *offset = offset.saturating_add(duration);
+    }
+
+    /// Returns the total virtual time that has passed on this clock.
+    pub fn elapsed(&self) -> Duration {
+        *self.offset.lock().unwrap()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Clock for TestClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        self.advance(duration);
+        std::future::ready(())
+    }
+}
@@ -0,0 +1,56 @@
+// Author: Jacques Murray
+
+//! A single callback summarizing the outcome of a retry loop.
+
+use crate::backoff::Backoff;
+use crate::circuit::CircuitGuard;
+use crate::clock::Clock;
+use crate::condition::RetryCondition;
+use crate::Retry;
+use std::fmt::Display;
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::time::Duration;
+
+/// A [`Retry`] that reports its outcome to a callback when it finishes.
+///
+/// Created by [`Retry::with_on_complete()`]. Awaiting it returns the retry's
+/// own result, after handing it to the callback along with the number of
+/// attempts made and the total time taken.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct OnComplete<S, O, C, K, B, H>
+where
+    S: Backoff,
+{
+    pub(crate) inner: Retry<S, O, C, K, B>,
+    pub(crate) hook: H,
+}
+
+/// The core retry logic, implemented via `IntoFuture` and followed by the
+/// completion callback.
+impl<S, O, C, K, B, H, F, T, E> IntoFuture for OnComplete<S, O, C, K, B, H>
+where
+    S: Backoff + Send + 'static,
+    K: Clock + Send + Sync + 'static,
+    B: CircuitGuard<E> + Send + 'static,
+    O: FnMut() -> F + Send + 'static,
+    C: RetryCondition<E> + Send + 'static,
+    H: FnOnce(&Result<T, E>, usize, Duration) + Send + 'static,
+    F: Future<Output = Result<T, E>> + Send,
+    E: Display + Send,
+    T: Send,
+{
+    type Output = Result<T, E>;
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> <OnComplete<S, O, C, K, B, H> as IntoFuture>::IntoFuture {
+        Box::pin(async move {
+            let OnComplete { inner, hook } = self;
+            let (result, stats, elapsed) = inner.execute_timed().await;
+            hook(&result, stats.attempts, elapsed);
+            result
+        })
+    }
+}
@@ -0,0 +1,401 @@
+// Author: Jacques Murray
+
+//! Retry conditions for deciding which errors should be retried.
+//!
+//! A condition is anything implementing [`RetryCondition`]. The retry loop
+//! consults it after every failed attempt; returning `false` stops the loop
+//! and returns the error immediately.
+//!
+//! # Built-in Conditions
+//!
+//! - **[`AlwaysRetry`]**: The default used by [`Retry::new()`](crate::Retry::new).
+//!   Retries every error.
+//! - **[`RetryableCondition`]**: Defers to the error's [`Retryable`] implementation.
+//!   Set via [`Retry::with_retryable_trait()`](crate::Retry::with_retryable_trait).
+//! - **Closures**: Any `FnMut(&E) -> bool` is a condition. Set via
+//!   [`Retry::with_condition()`](crate::Retry::with_condition).
+//!
+//! # Combinators
+//!
+//! Closure conditions can be composed with [`and()`], [`or()`], and [`not()`],
+//! and [`retry_all()`] and [`retry_none()`] provide the trivial conditions. Each
+//! returns a closure that can be passed to
+//! [`Retry::with_condition()`](crate::Retry::with_condition):
+//!
+//! ```rust
+//! use async_retry::condition::{not, or};
+//!
+//! #[derive(Debug)]
+//! enum ApiError {
+//!     Timeout,
+//!     RateLimited,
+//!     Unauthorized,
+//! }
+//!
+//! let is_timeout = |e: &ApiError| matches!(e, ApiError::Timeout);
+//! let is_rate_limited = |e: &ApiError| matches!(e, ApiError::RateLimited);
+//!
+//! let mut condition = or(is_timeout, is_rate_limited);
+//! assert!(condition(&ApiError::RateLimited));
+//! assert!(!condition(&ApiError::Unauthorized));
+//!
+//! let mut condition = not(|e: &ApiError| matches!(e, ApiError::Unauthorized));
+//! assert!(condition(&ApiError::Timeout));
+//! ```
+//!
+//! # Boxed Errors
+//!
+//! When the error is a boxed trait object such as `Box<dyn Error + Send + Sync>`,
+//! a condition usually needs to recover the concrete type with
+//! `downcast_ref()`:
+//!
+//! ```rust
+//! use std::error::Error;
+//! use std::io;
+//!
+//! let condition = |e: &Box<dyn Error + Send + Sync>| {
+//!     e.downcast_ref::<io::Error>()
+//!         .is_some_and(|e| e.kind() == io::ErrorKind::ConnectionReset)
+//! };
+//! ```
+//!
+//! For the common cases, [`on_type()`] and [`io_timeout()`] return ready-made
+//! closures for use with [`Retry::with_condition()`](crate::Retry::with_condition).
+//! Only the outermost error is inspected, not its [`source()`](std::error::Error::source) chain.
+
+use crate::timeout::RetryTimeout;
+use std::error::Error;
+use std::io;
+use std::time::Duration;
+
+/// Converts the [`RetryTimeout`] of an attempt cut off by a strict maximum
+/// duration into the operation's error type.
+pub(crate) type IntoTimeout<E> = fn(RetryTimeout) -> E;
+
+/// Decides whether a failed attempt should be retried.
+///
+/// This is implemented for every `FnMut(&E) -> bool` closure, so you rarely
+/// need to implement it yourself.
+pub trait RetryCondition<E> {
+    /// Returns `true` if the operation should be retried after `error`.
+    fn should_retry(&mut self, error: &E) -> bool;
+
+    /// Decides what to do after `error`, optionally overriding the next delay.
+    ///
+    /// The default maps [`should_retry()`](RetryCondition::should_retry) to
+    /// [`RetryAction::Retry`] or [`RetryAction::Stop`]. See [`ActionCondition`].
+    fn action(&mut self, error: &E) -> RetryAction {
+        if self.should_retry(error) {
+            RetryAction::Retry
+        } else {
+            RetryAction::Stop
+        }
+    }
+
+    /// Maps the last error when the loop stops because the maximum duration
+    /// ran out, rather than because the error wasn't retryable.
+    ///
+    /// The default returns `error` unchanged. See [`MapTimeoutError`].
+    fn on_timeout(&mut self, error: E) -> E {
+        error
+    }
+
+    /// Returns the budget that bounds each attempt, along with the conversion
+    /// for the error of an attempt cut off by it.
+    ///
+    /// Only [`StrictMaxDuration`] overrides this, and wrapping conditions
+    /// delegate it. The default returns `None`.
+    #[doc(hidden)]
+    fn attempt_deadline(&self) -> Option<(Duration, IntoTimeout<E>)> {
+        None
+    }
+}
+
+impl<E, F> RetryCondition<E> for F
+where
+    F: FnMut(&E) -> bool,
+{
+    fn should_retry(&mut self, error: &E) -> bool {
+        self(error)
+    }
+}
+
+/// The default retry condition used by [`Retry::new()`](crate::Retry::new),
+/// which retries all errors.
+///
+/// This is a marker type rather than a closure so that the default `Retry`
+/// can be named and awaited without specifying the error type up front.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysRetry;
+
+impl<E> RetryCondition<E> for AlwaysRetry {
+    fn should_retry(&mut self, _error: &E) -> bool {
+        true
+    }
+}
+
+/// Returns a condition that retries only when both `a` and `b` return `true`.
+///
+/// `b` is not called when `a` returns `false`.
+pub fn and<E, A, B>(mut a: A, mut b: B) -> impl FnMut(&E) -> bool
+where
+    A: FnMut(&E) -> bool,
+    B: FnMut(&E) -> bool,
+{
+    move |error: &E| a(error) && b(error)
+}
+
+/// Returns a condition that retries when either `a` or `b` returns `true`.
+///
+/// `b` is not called when `a` returns `true`.
+pub fn or<E, A, B>(mut a: A, mut b: B) -> impl FnMut(&E) -> bool
+where
+    A: FnMut(&E) -> bool,
+    B: FnMut(&E) -> bool,
+{
+    move |error: &E| a(error) || b(error)
+}
+
+/// Returns a condition that retries exactly when `condition` returns `false`.
+pub fn not<E, C>(mut condition: C) -> impl FnMut(&E) -> bool
+where
+    C: FnMut(&E) -> bool,
+{
+    move |error: &E| !condition(error)
+}
+
+/// Returns a condition that retries every error.
+///
+/// This behaves like [`AlwaysRetry`], but as a closure it can be composed
+/// with the other combinators.
+pub fn retry_all<E>() -> impl FnMut(&E) -> bool + Clone + Send + 'static {
+    |_: &E| true
+}
+
+/// Returns a condition that never retries, so the first error is returned.
+pub fn retry_none<E>() -> impl FnMut(&E) -> bool + Clone + Send + 'static {
+    |_: &E| false
+}
+
+/// What the retry loop should do after a failed attempt.
+///
+/// Returned by the closure passed to
+/// [`Retry::with_action_condition()`](crate::Retry::with_action_condition).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAction {
+    /// Retry after the backoff strategy's next delay.
+    Retry,
+    /// Retry after the given delay instead of the strategy's.
+    RetryAfter(std::time::Duration),
+    /// Stop retrying and return the error.
+    Stop,
+}
+
+/// A condition that returns a [`RetryAction`] rather than a `bool`.
+///
+/// Created by [`Retry::with_action_condition()`](crate::Retry::with_action_condition).
+#[derive(Debug, Clone, Copy)]
+pub struct ActionCondition<F>(pub(crate) F);
+
+impl<E, F> RetryCondition<E> for ActionCondition<F>
+where
+    F: FnMut(&E) -> RetryAction,
+{
+    fn should_retry(&mut self, error: &E) -> bool {
+        self.action(error) != RetryAction::Stop
+    }
+
+    fn action(&mut self, error: &E) -> RetryAction {
+        (self.0)(error)
+    }
+}
+
+/// A condition wrapper that maps the last error on timeout.
+///
+/// Created by [`Retry::with_timeout_error()`](crate::Retry::with_timeout_error).
+/// Retry decisions are delegated to the wrapped condition.
+#[derive(Debug, Clone)]
+pub struct MapTimeoutError<C, M> {
+    pub(crate) condition: C,
+    pub(crate) map: M,
+}
+
+impl<E, C, M> RetryCondition<E> for MapTimeoutError<C, M>
+where
+    C: RetryCondition<E>,
+    M: FnMut(E) -> E,
+{
+    fn should_retry(&mut self, error: &E) -> bool {
+        self.condition.should_retry(error)
+    }
+
+    fn action(&mut self, error: &E) -> RetryAction {
+        self.condition.action(error)
+    }
+
+    fn on_timeout(&mut self, error: E) -> E {
+        (self.map)(self.condition.on_timeout(error))
+    }
+
+    fn attempt_deadline(&self) -> Option<(Duration, IntoTimeout<E>)> {
+        self.condition.attempt_deadline()
+    }
+}
+
+/// A condition wrapper that also bounds each attempt by the maximum duration.
+///
+/// Created by [`Retry::with_strict_max_duration()`](crate::Retry::with_strict_max_duration).
+/// Retry decisions are delegated to the wrapped condition.
+#[derive(Debug, Clone)]
+pub struct StrictMaxDuration<C> {
+    pub(crate) condition: C,
+    pub(crate) max_duration: Duration,
+}
+
+impl<E, C> RetryCondition<E> for StrictMaxDuration<C>
+where
+    C: RetryCondition<E>,
+    E: From<RetryTimeout>,
+{
+    fn should_retry(&mut self, error: &E) -> bool {
+        self.condition.should_retry(error)
+    }
+
+    fn action(&mut self, error: &E) -> RetryAction {
+        self.condition.action(error)
+    }
+
+    fn on_timeout(&mut self, error: E) -> E {
+        self.condition.on_timeout(error)
+    }
+
+    fn attempt_deadline(&self) -> Option<(Duration, IntoTimeout<E>)> {
+        Some((self.max_duration, E::from))
+    }
+}
+
+/// Implemented by error types that know whether they are worth retrying.
+///
+/// Use [`Retry::with_retryable_trait()`](crate::Retry::with_retryable_trait)
+/// to have the retry loop consult [`is_retryable()`](Retryable::is_retryable)
+/// instead of a condition closure.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::Retryable;
+///
+/// #[derive(Debug)]
+/// enum DbError {
+///     Deadlock,
+///     ConstraintViolation,
+/// }
+///
+/// impl Retryable for DbError {
+///     fn is_retryable(&self) -> bool {
+///         matches!(self, DbError::Deadlock)
+///     }
+/// }
+/// ```
+pub trait Retryable {
+    /// Returns `true` if the operation that produced this error may succeed
+    /// on a later attempt.
+    fn is_retryable(&self) -> bool;
+}
+
+/// A retry condition that defers to the error's [`Retryable`] implementation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetryableCondition;
+
+impl<E: Retryable> RetryCondition<E> for RetryableCondition {
+    fn should_retry(&mut self, error: &E) -> bool {
+        error.is_retryable()
+    }
+}
+
+/// Boxed error types whose concrete type can be inspected by downcasting.
+///
+/// This is implemented for `Box<dyn Error>`, `Box<dyn Error + Send>`, and
+/// `Box<dyn Error + Send + Sync>`, and is used by [`on_type()`] and
+/// [`io_timeout()`].
+pub trait AsDynError {
+    /// Returns the boxed error as a `dyn Error` trait object.
+    fn as_dyn_error(&self) -> &(dyn Error + 'static);
+}
+
+impl AsDynError for Box<dyn Error> {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        &**self
+    }
+}
+
+impl AsDynError for Box<dyn Error + Send> {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        &**self
+    }
+}
+
+impl AsDynError for Box<dyn Error + Send + Sync> {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        &**self
+    }
+}
+
+/// Returns a condition that retries only when the boxed error is a `T`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_retry::{Retry, backoff::FixedDelay, condition};
+/// use std::error::Error;
+/// use std::time::Duration;
+///
+/// # async fn read_config() -> Result<(), Box<dyn Error + Send + Sync>> { Ok(()) }
+/// # async fn example() {
+/// let result = Retry::new(
+///     FixedDelay::new(Duration::from_secs(1)).take(3),
+///     move || async move { read_config().await }
+/// )
+/// .with_condition(condition::on_type::<std::io::Error, _>())
+/// .await;
+/// # }
+/// ```
+pub fn on_type<T, E>() -> impl FnMut(&E) -> bool + Clone + Send + 'static
+where
+    T: Error + 'static,
+    E: AsDynError,
+{
+    |error: &E| error.as_dyn_error().is::<T>()
+}
+
+/// Returns a condition that retries only when the boxed error is an
+/// [`io::Error`] of kind [`TimedOut`](io::ErrorKind::TimedOut).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_retry::{Retry, backoff::FixedDelay, condition};
+/// use std::error::Error;
+/// use std::time::Duration;
+///
+/// # async fn connect() -> Result<(), Box<dyn Error + Send + Sync>> { Ok(()) }
+/// # async fn example() {
+/// let result = Retry::new(
+///     FixedDelay::new(Duration::from_secs(1)).take(3),
+///     move || async move { connect().await }
+/// )
+/// .with_condition(condition::io_timeout())
+/// .await;
+/// # }
+/// ```
+pub fn io_timeout<E>() -> impl FnMut(&E) -> bool + Clone + Send + 'static
+where
+    E: AsDynError,
+{
+    |error: &E| {
+        error
+            .as_dyn_error()
+            .downcast_ref::<io::Error>()
+            .is_some_and(|e| e.kind() == io::ErrorKind::TimedOut)
+    }
+}
@@ -0,0 +1,66 @@
+// Author: Jacques Murray
+
+//! Operations that carry mutable context across attempts.
+
+use crate::backoff::Backoff;
+use crate::circuit::CircuitGuard;
+use crate::clock::Clock;
+use crate::condition::RetryCondition;
+use crate::Retry;
+use std::fmt::Display;
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+
+/// An operation that is handed a mutable context on every attempt.
+///
+/// Created by [`Retry::with_context()`]. The context is owned by the retry and
+/// persists across attempts, so each attempt sees what earlier ones recorded.
+/// Awaiting the `Retry` returns the final context alongside the result.
+#[derive(Debug, Clone)]
+pub struct Contextual<Ctx, Op> {
+    pub(crate) context: Ctx,
+    pub(crate) operation: Op,
+}
+
+/// The core retry logic, implemented via `IntoFuture` for operations with a
+/// context. The output also hands back the final context.
+impl<S, Ctx, Op, C, K, B, F, T, E> IntoFuture for Retry<S, Contextual<Ctx, Op>, C, K, B>
+where
+    S: Backoff + Send + 'static,
+    K: Clock + Send + Sync + 'static,
+    B: CircuitGuard<E> + Send + 'static,
+    Ctx: Send + 'static,
+    Op: FnMut(&mut Ctx) -> F + Send + 'static,
+    C: RetryCondition<E> + Send + 'static,
+    F: Future<Output = Result<T, E>> + Send,
+    E: Display + Send,
+    T: Send,
+{
+    type Output = (Result<T, E>, Ctx);
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> <Retry<S, Contextual<Ctx, Op>, C, K, B> as IntoFuture>::IntoFuture {
+        Box::pin(async move {
+            let Retry {
+                mut strategy,
+                operation:
+                    Contextual {
+                        mut context,
+                        mut operation,
+                    },
+                mut config,
+            } = self;
+            let result = config
+                .execute(
+                    &mut strategy,
+                    &mut || operation(&mut context),
+                    &mut |_: &T| true,
+                )
+                .await
+                .0;
+            (result, context)
+        })
+    }
+}
@@ -0,0 +1,98 @@
+// Author: Jacques Murray
+
+//! An error wrapper that reports how much retrying preceded the failure.
+
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// The final error of a retry loop, along with how it got there.
+///
+/// Returned by [`Retry::run_wrapped()`](crate::Retry::run_wrapped). Its
+/// `Display` reads `"failed after 5 attempts in 3.2s: {error}"`, which makes a
+/// single log line at the call site self-explanatory, and
+/// [`source()`](Error::source) returns the operation's own error.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_retry::{Retry, backoff::FixedDelay};
+/// use std::time::Duration;
+///
+/// # #[derive(Debug, Clone)]
+/// # struct MyError;
+/// # impl std::fmt::Display for MyError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+/// # }
+/// # impl std::error::Error for MyError {}
+/// # async fn fetch() -> Result<String, MyError> { Ok(String::new()) }
+/// # async fn example() {
+/// let result = Retry::new(
+///     FixedDelay::new(Duration::from_millis(100)).take(4),
+///     move || async move { fetch().await }
+/// )
+/// .run_wrapped()
+/// .await;
+///
+/// if let Err(e) = result {
+///     eprintln!("{}", e); // "failed after 5 attempts in 0.4s: ..."
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryError<E> {
+    error: E,
+    attempts: usize,
+    elapsed: Duration,
+}
+
+impl<E> RetryError<E> {
+    pub(crate) fn new(error: E, attempts: usize, elapsed: Duration) -> Self {
+        Self {
+            error,
+            attempts,
+            elapsed,
+        }
+    }
+
+    /// Returns the number of attempts made, including the first.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Returns the total time taken, as measured by the configured
+    /// [`Clock`](crate::Clock).
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns a reference to the operation's error.
+    pub fn inner(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the operation's error, discarding the retry details.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.attempts == 1 { "" } else { "s" };
+        write!(
+            f,
+            "failed after {} attempt{} in {:.1}s: {}",
+            self.attempts,
+            plural,
+            self.elapsed.as_secs_f64(),
+            self.error
+        )
+    }
+}
+
+impl<E: Error + 'static> Error for RetryError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
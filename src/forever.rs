@@ -0,0 +1,78 @@
+// Author: Jacques Murray
+
+//! Retrying until success, with a mandatory way to stop.
+
+use crate::backoff::Backoff;
+use crate::circuit::NoCircuitBreaker;
+use crate::clock::SystemClock;
+use crate::condition::AlwaysRetry;
+use crate::timeout::HardTimeout;
+use crate::Retry;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A retry that keeps going until it succeeds, waiting for a way to stop.
+///
+/// Created by [`Retry::forever()`]. A `Forever` can't be awaited: it must
+/// first be given a stop mechanism, which turns it into an ordinary [`Retry`]
+/// (or [`HardTimeout`]) that can be configured further and awaited. This
+/// makes an unbounded retry loop an explicit choice rather than an accident.
+///
+/// Forgetting the stop mechanism is a compile error:
+///
+/// ```rust,compile_fail
+/// use async_retry::{Retry, backoff::FixedDelay};
+/// use std::time::Duration;
+///
+/// # async fn example() {
+/// let result = Retry::forever(
+///     FixedDelay::new(Duration::from_secs(1)),
+///     || async { Err::<(), _>("still down") },
+/// )
+/// .await;
+/// # }
+/// ```
+#[must_use = "a `Forever` does nothing until it is given a stop mechanism and awaited"]
+#[derive(Debug, Clone)]
+pub struct Forever<S, O> {
+    pub(crate) strategy: S,
+    pub(crate) operation: O,
+}
+
+impl<S, O> Forever<S, O>
+where
+    S: Backoff,
+{
+    /// Retries until success or until `flag` is set, as
+    /// [`Retry::with_abort_flag()`].
+    pub fn with_abort_flag(self, flag: Arc<AtomicBool>) -> Retry<S, O, AlwaysRetry> {
+        self.into_retry().with_abort_flag(flag)
+    }
+
+    /// Retries until success or until `max_duration` has passed, as
+    /// [`Retry::with_max_duration()`].
+    pub fn with_max_duration(self, max_duration: Duration) -> Retry<S, O, AlwaysRetry> {
+        self.into_retry().with_max_duration(max_duration)
+    }
+
+    /// Retries until success, cancelling the whole loop after `timeout`, as
+    /// [`Retry::with_hard_timeout()`].
+    pub fn with_hard_timeout<F, T, E>(
+        self,
+        timeout: Duration,
+    ) -> HardTimeout<S, O, AlwaysRetry, SystemClock, NoCircuitBreaker>
+    where
+        O: FnMut() -> F,
+        F: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        self.into_retry().with_hard_timeout(timeout)
+    }
+
+    fn into_retry(self) -> Retry<S, O, AlwaysRetry> {
+        Retry::new(self.strategy, self.operation)
+    }
+}
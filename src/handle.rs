@@ -0,0 +1,93 @@
+// Author: Jacques Murray
+
+//! Live progress of a running retry, readable from other tasks.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "tokio-timer")]
+use std::time::{Duration, Instant};
+
+/// A handle to the live state of a retry run.
+///
+/// Attach it with [`Retry::with_handle()`](crate::Retry::with_handle), or get
+/// one from [`Retry::spawn_with_handle()`](crate::Retry::spawn_with_handle).
+/// Clones share the same state, so one clone can be kept to query the run
+/// while it is in progress, e.g. for a health endpoint or a progress display.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_retry::RetryHandle;
+///
+/// let handle = RetryHandle::new();
+/// assert_eq!(handle.attempts_made(), 0);
+/// assert_eq!(handle.last_error(), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RetryHandle {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    attempts_made: AtomicUsize,
+    last_error: Mutex<Option<String>>,
+}
+
+impl RetryHandle {
+    /// Creates a handle for a run that hasn't started.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of attempts started so far, including the one in
+    /// progress.
+    pub fn attempts_made(&self) -> usize {
+        self.inner.attempts_made.load(Ordering::Relaxed)
+    }
+
+    /// Returns the most recent attempt's error, formatted with `Display`.
+    ///
+    /// This is `None` until an attempt fails, and keeps the last failure
+    /// even once a later attempt succeeds.
+    pub fn last_error(&self) -> Option<String> {
+        self.inner.last_error.lock().unwrap().clone()
+    }
+
+    pub(crate) fn record_attempt(&self, attempt: usize) {
+        self.inner.attempts_made.store(attempt, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_error(&self, error: String) {
+        *self.inner.last_error.lock().unwrap() = Some(error);
+    }
+}
+
+/// The delay before the next attempt, published just before it starts.
+///
+/// Sent to the channel set with
+/// [`Retry::with_countdown_channel()`](crate::Retry::with_countdown_channel),
+/// so a UI can show e.g. "Retrying in 3... 2... 1...". It requires the
+/// `tokio-timer` feature flag.
+#[cfg(feature = "tokio-timer")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Countdown {
+    /// The attempt that just failed, starting from 1.
+    pub attempt: usize,
+    /// How long the retry will wait before the next attempt.
+    pub delay: Duration,
+    /// When the wait started, as reported by the retry's
+    /// [`Clock`](crate::Clock).
+    pub started_at: Instant,
+}
+
+#[cfg(feature = "tokio-timer")]
+impl Countdown {
+    /// Returns how much of the delay is left at `now`, or zero once it has
+    /// passed.
+    pub fn remaining_at(&self, now: Instant) -> Duration {
+        self.delay
+            .saturating_sub(now.saturating_duration_since(self.started_at))
+    }
+}
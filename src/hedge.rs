@@ -0,0 +1,72 @@
+// Author: Jacques Murray
+
+//! Hedged attempts for reducing tail latency.
+//!
+//! A hedged attempt starts the operation, and if it has not completed after
+//! the hedge delay, starts a second concurrent invocation. The first one to
+//! succeed wins; the other is dropped (cancelled).
+
+use crate::clock::Clock;
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+use std::task::Poll;
+use std::time::Duration;
+
+/// Runs a single (possibly hedged) attempt of `operation`.
+///
+/// If the primary invocation fails before the hedge delay elapses, its error
+/// is returned without hedging. Once both invocations are in flight, the first
+/// success is returned; if the first to finish fails, the other is awaited and
+/// its result returned instead.
+pub(crate) async fn run_hedged<O, F, T, E, K>(
+    operation: &mut O,
+    clock: &K,
+    hedge_delay: Duration,
+) -> Result<T, E>
+where
+    O: FnMut() -> F,
+    F: Future<Output = Result<T, E>>,
+    K: Clock,
+{
+    let mut primary = pin!(operation());
+    let mut timer = pin!(clock.sleep(hedge_delay));
+
+    // Race the primary invocation against the hedge timer.
+    let early = poll_fn(|cx| {
+        if let Poll::Ready(result) = primary.as_mut().poll(cx) {
+            return Poll::Ready(Some(result));
+        }
+        if timer.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    })
+    .await;
+
+    if let Some(result) = early {
+        return result;
+    }
+
+    #[cfg(feature = "logging")]
+    log::trace!("Launching hedged attempt after {:?}", hedge_delay);
+
+    let hedge = pin!(operation());
+    let mut in_flight = [Some(primary), Some(hedge)];
+
+    poll_fn(|cx| {
+        for slot in 0..in_flight.len() {
+            let Some(fut) = in_flight[slot].as_mut() else {
+                continue;
+            };
+            if let Poll::Ready(result) = fut.as_mut().poll(cx) {
+                in_flight[slot] = None;
+                // Return on success, or on failure once nothing else is in flight.
+                if result.is_ok() || in_flight.iter().all(Option::is_none) {
+                    return Poll::Ready(result);
+                }
+            }
+        }
+        Poll::Pending
+    })
+    .await
+}
@@ -0,0 +1,215 @@
+// Author: Jacques Murray
+
+//! Ready-made retry conditions for HTTP requests made with `reqwest`.
+//!
+//! This module requires the `reqwest` feature flag. It packages the usual
+//! "retry transient failures" policy for HTTP clients:
+//!
+//! - Connection errors and timeouts are retried.
+//! - The transient statuses 408, 429, 500, 502, 503 and 504 are retried.
+//! - A `Retry-After` header on a failed response overrides the next delay.
+//! - Everything else (e.g., 404, or a request that failed to build) stops.
+//!
+//! Make the operation return [`HttpError`], converting unsuccessful
+//! responses with [`HttpError::check()`], and pass
+//! [`retry_on_transient_status()`] to
+//! [`Retry::with_action_condition()`](crate::Retry::with_action_condition).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use async_retry::{Retry, backoff::ExponentialBackoff};
+//! use async_retry::http::{retry_on_transient_status, HttpError};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), HttpError> {
+//! let client = reqwest::Client::new();
+//!
+//! let body = Retry::new(
+//!     ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(5),
+//!     move || {
+//!         let client = client.clone();
+//!         async move {
+//!             let response = client.get("https://example.com/data").send().await?;
+//!             Ok(HttpError::check(response)?.text().await?)
+//!         }
+//!     },
+//! )
+//! .with_action_condition(retry_on_transient_status())
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::condition::RetryAction;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Response, StatusCode};
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// An error from an HTTP request: either the request itself failed, or the
+/// server answered with an unsuccessful status.
+#[derive(Debug)]
+pub enum HttpError {
+    /// The request could not be completed (e.g., a connection error).
+    Request(reqwest::Error),
+    /// The server responded with a non-success status.
+    Status {
+        /// The response status.
+        status: StatusCode,
+        /// The delay requested by the response's `Retry-After` header, if any.
+        retry_after: Option<Duration>,
+    },
+}
+
+impl HttpError {
+    /// Returns the response if its status is a success, or an
+    /// [`HttpError::Status`] capturing the status and `Retry-After` header.
+    pub fn check(response: Response) -> Result<Response, HttpError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        Err(HttpError::Status {
+            status,
+            retry_after: parse_retry_after(response.headers()),
+        })
+    }
+
+    /// Returns the HTTP status associated with the error, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            HttpError::Request(e) => e.status(),
+            HttpError::Status { status, .. } => Some(*status),
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::Request(e) => write!(f, "request failed: {}", e),
+            HttpError::Status { status, .. } => write!(f, "unsuccessful response: {}", status),
+        }
+    }
+}
+
+impl Error for HttpError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HttpError::Request(e) => Some(e),
+            HttpError::Status { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for HttpError {
+    fn from(e: reqwest::Error) -> Self {
+        HttpError::Request(e)
+    }
+}
+
+/// Returns `true` for statuses that usually indicate a transient failure:
+/// 408, 429, 500, 502, 503 and 504.
+pub fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Reads a `Retry-After` header given in seconds.
+///
+/// The HTTP-date form of the header is not supported and yields `None`, in
+/// which case the retry falls back to the backoff strategy's delay.
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// A condition that retries transient HTTP failures, honoring `Retry-After`.
+///
+/// Pass it to [`Retry::with_action_condition()`](crate::Retry::with_action_condition).
+/// See the [module documentation](self) for the exact policy.
+pub fn retry_on_transient_status() -> impl FnMut(&HttpError) -> RetryAction + Clone + Send + 'static
+{
+    |e: &HttpError| match e {
+        HttpError::Request(e) if e.is_connect() || e.is_timeout() => RetryAction::Retry,
+        HttpError::Request(e) => match e.status() {
+            Some(status) if is_transient_status(status) => RetryAction::Retry,
+            _ => RetryAction::Stop,
+        },
+        HttpError::Status {
+            status,
+            retry_after,
+        } if is_transient_status(*status) => match retry_after {
+            Some(delay) => RetryAction::RetryAfter(*delay),
+            None => RetryAction::Retry,
+        },
+        HttpError::Status { .. } => RetryAction::Stop,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_is_transient_status() {
+        for code in [408, 429, 500, 502, 503, 504] {
+            assert!(is_transient_status(StatusCode::from_u16(code).unwrap()));
+        }
+        for code in [200, 301, 400, 401, 403, 404, 409, 501, 505] {
+            assert!(!is_transient_status(StatusCode::from_u16(code).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_retry_on_transient_status() {
+        let mut condition = retry_on_transient_status();
+        let status = |code, retry_after| HttpError::Status {
+            status: StatusCode::from_u16(code).unwrap(),
+            retry_after,
+        };
+
+        assert_eq!(condition(&status(503, None)), RetryAction::Retry);
+        assert_eq!(
+            condition(&status(429, Some(Duration::from_secs(2)))),
+            RetryAction::RetryAfter(Duration::from_secs(2))
+        );
+        assert_eq!(condition(&status(404, None)), RetryAction::Stop);
+        // Retry-After on a permanent failure doesn't make it retryable
+        assert_eq!(
+            condition(&status(400, Some(Duration::from_secs(2)))),
+            RetryAction::Stop
+        );
+    }
+}
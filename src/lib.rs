@@ -24,7 +24,8 @@
 //! ```
 //!
 //! **Note:** You *must* enable a timer feature for this crate:
-//! `features = ["tokio-timer"]` or `features = ["async-std-timer"]`.
+//! `features = ["tokio-timer"]`, `features = ["async-std-timer"]`, or
+//! `features = ["wasm-timer"]` for browsers.
 //!
 //! ### Example: Simple Retry
 //!
@@ -123,37 +124,109 @@
 //!     }
 //! }
 //! ```
+//!
+//! ## Retries and Attempts
+//!
+//! Limits on a backoff strategy, such as `with_max_retries(3)` or `.take(3)`,
+//! count *retries*: the first attempt is free, so the operation runs up to
+//! 4 times. To limit the *total* number of attempts instead, use
+//! [`Retry::with_max_attempts()`], where `with_max_attempts(3)` means at most
+//! 3 runs. When both are set, whichever is reached first ends the loop.
+
+// Fail early, with one clear message, if no timer feature is enabled.
+#[cfg(not(any(
+    feature = "tokio-timer",
+    feature = "async-std-timer",
+    feature = "wasm-timer"
+)))]
+compile_error!(
+    "async-retry needs a timer feature to sleep between attempts. Enable one of: \
+     `tokio-timer` (Tokio), `async-std-timer` (async-std), or `wasm-timer` (browsers), \
+     e.g. `async-retry = { version = \"0.1\", features = [\"tokio-timer\"] }`."
+);
 
 // Public modules
 pub mod backoff;
+mod batch;
+mod budget;
+pub mod circuit;
+pub mod clock;
+mod complete;
+pub mod condition;
+mod context;
+mod error;
+mod forever;
+mod handle;
+mod hedge;
+#[cfg(feature = "reqwest")]
+pub mod http;
+mod local;
+mod macros;
+#[cfg(feature = "global-metrics")]
+pub mod metrics;
+mod poll;
+mod single_flight;
 mod sleep;
+mod timeout;
+mod unwind;
+mod validate;
 
 // Public re-exports for easier use
-pub use backoff::{Backoff, ExponentialBackoff, FibonacciBackoff, FixedDelay};
+pub use backoff::{
+    AdaptivePoll, Backoff, BackoffBuilder, BackoffError, DefaultBackoff, ExponentialBackoff,
+    FibonacciBackoff, FixedDelay, FromSchedule, NoDelay, Schedule,
+};
+pub use batch::retry_all;
+pub use budget::Budgeted;
+
+pub use circuit::{CircuitBreaker, CircuitGuard, NoCircuitBreaker};
+pub use clock::{Clock, SleepFn, SystemClock};
+pub use complete::OnComplete;
+pub use condition::{
+    ActionCondition, AlwaysRetry, MapTimeoutError, RetryAction, RetryCondition, Retryable,
+    RetryableCondition, StrictMaxDuration,
+};
+pub use context::Contextual;
+pub use error::RetryError;
+pub use forever::Forever;
+#[cfg(feature = "tokio-timer")]
+pub use handle::Countdown;
+pub use handle::RetryHandle;
+pub use local::LocalRetry;
+pub use poll::{poll_until, NotReady, PollUntil};
+pub use single_flight::{SingleFlight, SingleFlightRetry};
+pub use timeout::{HardTimeout, RetryTimeout};
+pub use unwind::{CatchUnwind, PanicError};
+pub use validate::{InvalidValue, Validated};
 
 #[cfg(feature = "jitter")]
-pub use backoff::Jitter;
+pub use backoff::{
+    Jitter, JitterSource, JitteredExponentialBackoff, RandomDelay, SeededSource, ThreadRngSource,
+    WeightedBackoff,
+};
 
-use std::error::Error;
+#[cfg(feature = "test-util")]
+pub use clock::TestClock;
+
+use std::fmt::Display;
 use std::future::Future;
 use std::future::IntoFuture;
-use std::future::IntoFuture;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-
-/// A predicate function that always returns true, retryable for all errors.
-fn default_condition(_: &dyn Error) -> bool {
-    true
-}
+use validate::Validator;
 
 /// The main builder struct for retryable operations.
 ///
 /// `Retry` provides a fluent builder API for configuring retry behavior. It is generic
-/// over three type parameters:
+/// over five type parameters:
 ///
 /// - `S`: The backoff strategy (implements [`Backoff`])
 /// - `O`: The operation closure that returns a future
 /// - `C`: The condition function that determines if an error should be retried
+/// - `K`: The [`Clock`] used for timing and sleeping (defaults to [`SystemClock`])
+/// - `B`: The [`CircuitGuard`] consulted around each attempt (defaults to [`NoCircuitBreaker`])
 ///
 /// # Type Parameters
 ///
@@ -163,12 +236,63 @@ fn default_condition(_: &dyn Error) -> bool {
 /// # Builder Methods
 ///
 /// - [`new()`](Retry::new) - Creates a new retry instance with default "retry all" behavior
+/// - [`default_policy()`](Retry::default_policy) - Creates a retry instance using the recommended default backoff
+/// - [`forever()`](Retry::forever) - Creates a retry instance that retries until success, once given a way to stop
+/// - [`with_state()`](Retry::with_state) - Creates a retry instance that passes shared state to each attempt
+/// - [`with_context()`](Retry::with_context) - Creates a retry instance that passes a mutable context to each attempt
+/// - [`with_shared()`](Retry::with_shared) - Creates a retry instance whose operation and condition share state
+/// - [`with_budget()`](Retry::with_budget) - Creates a retry instance that passes the remaining time budget to each attempt
+/// - [`with_idempotency_key()`](Retry::with_idempotency_key) - Creates a retry instance that passes one idempotency key to every attempt
 /// - [`with_condition()`](Retry::with_condition) - Sets a custom retry condition
+/// - [`with_action_condition()`](Retry::with_action_condition) - Sets a condition that can also override the next delay
+/// - [`with_enabled()`](Retry::with_enabled) - Turns retrying off, making a single attempt
+/// - [`at_least_once()`](Retry::at_least_once) - Checks that the strategy allows at least one retry
 /// - [`with_max_duration()`](Retry::with_max_duration) - Sets a maximum total duration
+/// - [`with_min_one_retry()`](Retry::with_min_one_retry) - Shortens the first delay to fit the maximum duration rather than not retrying
+/// - [`with_strict_max_duration()`](Retry::with_strict_max_duration) - Sets a maximum total duration that also cuts off slow attempts
+/// - [`with_duration_fraction()`](Retry::with_duration_fraction) - Caps the total duration at a fraction of the time left before a deadline
+/// - [`with_retryable_trait()`](Retry::with_retryable_trait) - Retries based on the error's [`Retryable`] impl
+/// - [`with_validator()`](Retry::with_validator) - Retries `Ok` values that fail a validation check
+/// - [`with_timeout_error()`](Retry::with_timeout_error) - Maps the last error when the maximum duration runs out
+/// - [`with_clock()`](Retry::with_clock) - Replaces the time source used for timing and sleeping
+/// - [`with_sleep_fn()`](Retry::with_sleep_fn) - Replaces how the retry sleeps between attempts
+/// - [`with_hedging()`](Retry::with_hedging) - Races a second invocation against a slow attempt
+/// - [`with_catch_unwind()`](Retry::with_catch_unwind) - Converts panics in the operation into retryable errors
+/// - [`with_span()`](Retry::with_span) - Runs every attempt inside a `tracing` span (with `tracing`)
+/// - [`with_startup_splay()`](Retry::with_startup_splay) - Delays the first attempt by a random amount (with `jitter`)
+/// - [`with_splay_source()`](Retry::with_splay_source) - Sets the source of randomness for the startup splay (with `jitter`)
+/// - [`with_before_attempt()`](Retry::with_before_attempt) - Runs a hook before every attempt
+/// - [`with_delay_transform()`](Retry::with_delay_transform) - Adjusts each backoff delay before sleeping
+/// - [`with_latency_aware_delay()`](Retry::with_latency_aware_delay) - Waits at least as long as the last attempt took
+/// - [`with_attempt_counter()`](Retry::with_attempt_counter) - Publishes the current attempt number to a shared counter
+/// - [`with_handle()`](Retry::with_handle) - Publishes live progress to a [`RetryHandle`]
+/// - [`with_countdown_channel()`](Retry::with_countdown_channel) - Publishes each upcoming delay to a `watch` channel (with `tokio-timer`)
+/// - [`with_abort_flag()`](Retry::with_abort_flag) - Stops retrying once a shared flag is set
+/// - [`with_wakeup_signal()`](Retry::with_wakeup_signal) - Cuts a backoff sleep short when a future resolves
+/// - [`with_spin_guard()`](Retry::with_spin_guard) - Stops a strategy that keeps retrying without delay
+/// - [`with_max_attempts()`](Retry::with_max_attempts) - Limits the total number of attempts, including the first
+/// - [`with_operation_name()`](Retry::with_operation_name) - Names the operation in structured logs
+/// - [`with_log_level()`](Retry::with_log_level) - Sets the level of per-attempt failure logs (with `logging`)
+/// - [`with_degraded_threshold()`](Retry::with_degraded_threshold) - Flags successes that needed many attempts
+/// - [`with_record_delays()`](Retry::with_record_delays) - Records the delays slept, for [`run_with_stats()`](Retry::run_with_stats)
+/// - [`with_circuit_breaker()`](Retry::with_circuit_breaker) - Fails fast while a shared circuit is open
+/// - [`with_hard_timeout()`](Retry::with_hard_timeout) - Cancels the whole retry if it runs past a deadline
+/// - [`with_on_complete()`](Retry::with_on_complete) - Reports the final outcome to a callback once
+/// - [`run()`](Retry::run) - Runs the retry; the same as awaiting it directly
+/// - [`run_into()`](Retry::run_into) - Runs the retry, converting the final error with `From`
+/// - [`run_wrapped()`](Retry::run_wrapped) - Runs the retry, wrapping the final error in a [`RetryError`]
+/// - [`local()`](Retry::local) - Awaits without requiring `Send` (single-threaded runtimes)
+/// - [`with_single_flight()`](Retry::with_single_flight) - Shares one run between concurrent calls with the same key
+/// - [`run_with()`](Retry::run_with) - Runs an operation without consuming the `Retry`, so it can be reused
+/// - [`spawn()`](Retry::spawn) - Runs the retry in the background on the Tokio runtime (with `tokio-timer`)
+/// - [`spawn_with_handle()`](Retry::spawn_with_handle) - Like `spawn()`, also returning a [`RetryHandle`] (with `tokio-timer`)
+/// - [`with_background_upgrade()`](Retry::with_background_upgrade) - Returns a partial success at once and keeps retrying for the full result in the background (with `tokio-timer`)
 ///
 /// # Execution
 ///
-/// `Retry` implements [`IntoFuture`], which means you can `.await` it directly:
+/// `Retry` implements [`IntoFuture`], which means you can `.await` it directly.
+/// Use [`run_with_attempts()`](Retry::run_with_attempts) to also get the number
+/// of attempts it took to succeed:
 ///
 /// ```rust,no_run
 /// # use async_retry::{Retry, backoff::FixedDelay};
@@ -186,6 +310,13 @@ fn default_condition(_: &dyn Error) -> bool {
 /// # }
 /// ```
 ///
+/// # Error Requirements
+///
+/// The error type `E` only needs to be `Display + Send`. `Display` is used when
+/// the `logging` feature reports failed attempts. It does not need to implement
+/// [`std::error::Error`], so boxed trait objects such as
+/// `Box<dyn std::error::Error + Send + Sync>` work as-is.
+///
 /// # Closure Requirements
 ///
 /// The operation closure must:
@@ -211,14 +342,83 @@ fn default_condition(_: &dyn Error) -> bool {
 /// # }
 /// ```
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct Retry<S, O, C>
+pub struct Retry<S, O, C, K = SystemClock, B = NoCircuitBreaker>
 where
     S: Backoff,
 {
     strategy: S,
     operation: O,
+    config: RetryConfig<C, K, B>,
+}
+
+/// Everything about a `Retry` except the strategy and the operation.
+///
+/// Keeping this separate lets the retry loop borrow the configuration while
+/// driving a strategy and operation it doesn't own, which is what allows a
+/// configured `Retry` to be reused via [`Retry::run_with()`].
+struct RetryConfig<C, K, B> {
     condition: C,
+    clock: K,
+    circuit_breaker: B,
+    options: RetryOptions,
+}
+
+/// Details about a completed retry loop.
+///
+/// Returned by [`Retry::run_with_stats()`] alongside the final result, whether
+/// the operation succeeded or not.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RetryStats {
+    /// The number of attempts made, including the first.
+    pub attempts: usize,
+    /// The delays actually slept between attempts, in order, including any
+    /// jitter. This is `None` unless
+    /// [`with_record_delays()`](Retry::with_record_delays) was set.
+    pub delays: Option<Vec<Duration>>,
+    /// Whether the operation succeeded, but only after more attempts than the
+    /// threshold set with [`with_degraded_threshold()`](Retry::with_degraded_threshold).
+    pub degraded: bool,
+    /// The total time spent running the operation, across all attempts.
+    pub total_exec: Duration,
+    /// The total time spent sleeping between attempts, including any startup
+    /// splay.
+    ///
+    /// Both totals are measured with the configured [`Clock`], so with a
+    /// `TestClock` they reflect virtual time.
+    pub total_sleep: Duration,
+}
+
+/// The settings of a `RetryConfig` that don't depend on any type parameters.
+#[derive(Default)]
+struct RetryOptions {
     max_duration: Option<Duration>,
+    min_one_retry: bool,
+    hedge_delay: Option<Duration>,
+    before_attempt: Option<Box<dyn FnMut(usize) + Send>>,
+    delay_transform: Option<Box<dyn FnMut(Duration, usize) -> Duration + Send>>,
+    latency_aware_delay: bool,
+    #[cfg_attr(not(feature = "logging"), allow(dead_code))]
+    operation_name: Option<String>,
+    #[cfg(feature = "logging")]
+    log_level: Option<log::Level>,
+    record_delays: bool,
+    attempt_counter: Option<Arc<AtomicUsize>>,
+    handle: Option<RetryHandle>,
+    #[cfg(feature = "tokio-timer")]
+    countdown: Option<tokio::sync::watch::Sender<Option<Countdown>>>,
+    degraded_threshold: Option<usize>,
+    retries_disabled: bool,
+    spin_guard: Option<usize>,
+    max_attempts: Option<usize>,
+    abort_flag: Option<Arc<AtomicBool>>,
+    #[cfg(feature = "tracing")]
+    span: Option<tracing::Span>,
+    wakeup_signal: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    #[cfg(feature = "jitter")]
+    startup_splay: Option<Duration>,
+    #[cfg(feature = "jitter")]
+    splay_source: Option<Box<dyn JitterSource + Send>>,
 }
 
 // Implementation block for creating a new Retry with the default condition.
@@ -237,6 +437,12 @@ where
     ///
     /// A `Retry` builder that can be configured further or awaited directly.
     ///
+    /// The operation always runs at least once; the strategy only controls
+    /// the retries after that. A strategy that yields no delays, such as
+    /// `FixedDelay::new(d).take(0)`, therefore means a single attempt and no
+    /// retries. Use [`at_least_once()`](Retry::at_least_once) to reject such
+    /// strategies instead.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -260,6 +466,37 @@ where
     /// # }
     /// ```
     ///
+    /// An `async fn` that takes no arguments is already an operation, so it
+    /// can be passed by name without wrapping it in a closure:
+    ///
+    /// ```rust,no_run
+    /// # use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// # use std::time::Duration;
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// async fn fetch_data() -> Result<String, MyError> {
+    ///     // ...
+    /// #   Ok(String::new())
+    /// }
+    ///
+    /// # async fn example() {
+    /// let strategy = ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(5);
+    /// let result = Retry::new(strategy, fetch_data).await;
+    /// # }
+    /// ```
+    ///
+    /// An `async fn` with arguments still needs a closure to supply them,
+    /// e.g. `move || fetch_user(id)`.
+    ///
+    /// The operation must return the same future type on every call. To pick
+    /// between different `async` blocks or functions at runtime, box them as
+    /// `Pin<Box<dyn Future<Output = Result<T, E>> + Send>>`; add `+ Send` so
+    /// the `Retry` itself can still be awaited from multi-threaded runtimes
+    /// (or use [`local()`](Retry::local) if the futures aren't `Send`).
+    ///
     /// # See Also
     ///
     /// * [`with_condition()`](Retry::with_condition) - Add custom retry logic
@@ -268,14 +505,345 @@ where
         Self {
             strategy,
             operation,
-            condition: AlwaysRetry,
-            max_duration: None,
+            config: RetryConfig {
+                condition: AlwaysRetry,
+                clock: SystemClock,
+                circuit_breaker: NoCircuitBreaker,
+                options: RetryOptions::default(),
+            },
+        }
+    }
+
+    /// Creates a retry that retries all errors for as long as it takes.
+    ///
+    /// Paired with an infinite strategy, such as a [`FixedDelay`] or an
+    /// [`ExponentialBackoff`] capped with `with_max_delay()`, the operation is
+    /// retried until it succeeds. Because that can be a loop that never ends,
+    /// the returned [`Forever`] can't be awaited until it is given a stop
+    /// mechanism: an abort flag, a maximum duration, or a hard timeout. A
+    /// finite strategy still ends the loop when it runs out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::sync::atomic::AtomicBool;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn connect() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let shutting_down = Arc::new(AtomicBool::new(false));
+    ///
+    /// let result = Retry::forever(
+    ///     ExponentialBackoff::new(Duration::from_millis(100))
+    ///         .with_max_delay(Duration::from_secs(30)),
+    ///     move || async move { connect().await }
+    /// )
+    /// .with_abort_flag(shutting_down.clone())
+    /// .await;
+    /// # }
+    /// ```
+    pub fn forever(strategy: S, operation: O) -> Forever<S, O> {
+        Forever {
+            strategy,
+            operation,
         }
     }
 }
 
+// Implementation block for creating a new Retry with the default policy.
+impl<O> Retry<DefaultBackoff, O, AlwaysRetry> {
+    /// Creates a new `Retry` instance using the crate's recommended default policy.
+    ///
+    /// The policy is [`backoff::default_backoff()`]: exponential backoff starting
+    /// at 100ms, limited to 5 retries (6 attempts in total), with full jitter
+    /// when the `jitter` feature is enabled. All errors are retried.
+    ///
+    /// Use this to keep retry behavior consistent across a codebase without
+    /// repeating the same strategy configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::Retry;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn fetch_data() -> Result<String, MyError> { Ok(String::new()) }
+    /// # async fn example() {
+    /// let result = Retry::default_policy(move || async move { fetch_data().await }).await;
+    /// # }
+    /// ```
+    pub fn default_policy(operation: O) -> Self {
+        Self::new(backoff::default_backoff(), operation)
+    }
+}
+
+// Implementation block for creating a new Retry over shared state or context.
+impl<S> Retry<S, (), AlwaysRetry>
+where
+    S: Backoff,
+{
+    /// Creates a new `Retry` instance that hands shared state to every attempt.
+    ///
+    /// The operation receives its own `Arc<T>` on each attempt, so it can move
+    /// the state into an `async move` block without cloning it by hand. This
+    /// replaces the usual pattern of cloning an `Arc` both outside and inside
+    /// the operation closure. All errors are retried, as with [`Retry::new()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # struct Client;
+    /// # impl Client { async fn get(&self, _: &str) -> Result<String, MyError> { Ok(String::new()) } }
+    /// # async fn example() {
+    /// let client = Arc::new(Client);
+    ///
+    /// let result = Retry::with_state(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(3),
+    ///     client,
+    ///     |client| async move { client.get("/users").await }
+    /// )
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_state<T, Op, F>(
+        strategy: S,
+        state: Arc<T>,
+        mut operation: Op,
+    ) -> Retry<S, impl FnMut() -> F, AlwaysRetry>
+    where
+        T: ?Sized,
+        Op: FnMut(Arc<T>) -> F,
+    {
+        Retry::new(strategy, move || operation(Arc::clone(&state)))
+    }
+
+    /// Creates a new `Retry` instance that tells each attempt how much of
+    /// `max_duration` is left.
+    ///
+    /// This sets [`with_max_duration()`](Retry::with_max_duration) and passes
+    /// the remaining budget to the operation on every attempt, so an operation
+    /// whose work scales with the time available can pick a cheaper path, or
+    /// a shorter timeout of its own, as the budget runs down. The first
+    /// attempt receives the full `max_duration`. All errors are retried, as
+    /// with [`Retry::new()`].
+    ///
+    /// The budget is measured with the [`Clock`], starting at the first
+    /// attempt, so awaiting the `Retry` requires the clock to be `Clone`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn full_search() -> Result<Vec<String>, MyError> { Ok(Vec::new()) }
+    /// # async fn cached_search() -> Result<Vec<String>, MyError> { Ok(Vec::new()) }
+    /// # async fn example() {
+    /// let result = Retry::with_budget(
+    ///     FixedDelay::new(Duration::from_millis(100)),
+    ///     Duration::from_secs(5),
+    ///     |remaining| async move {
+    ///         if remaining < Duration::from_secs(1) {
+    ///             cached_search().await
+    ///         } else {
+    ///             full_search().await
+    ///         }
+    ///     }
+    /// )
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_budget<Op, F>(
+        strategy: S,
+        max_duration: Duration,
+        operation: Op,
+    ) -> Retry<S, Budgeted<Op>, AlwaysRetry>
+    where
+        Op: FnMut(Duration) -> F,
+    {
+        Retry::new(strategy, Budgeted { operation }).with_max_duration(max_duration)
+    }
+
+    /// Creates a new `Retry` instance that passes the same idempotency key to
+    /// every attempt.
+    ///
+    /// `generate` is called exactly once, when the `Retry` is created, and its
+    /// key is handed to each attempt. Sending it with a request (e.g., as an
+    /// `Idempotency-Key` header) lets the server recognize a retry of a
+    /// request it already processed, so retrying an operation that isn't
+    /// naturally idempotent, such as a payment, can't apply it twice. All
+    /// errors are retried, as with [`Retry::new()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # fn new_uuid() -> String { String::new() }
+    /// # async fn charge(_amount: u64, _key: &str) -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::with_idempotency_key(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(3),
+    ///     new_uuid,
+    ///     |key| async move { charge(100, &key).await }
+    /// )
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_idempotency_key<G, Op, F>(
+        strategy: S,
+        generate: G,
+        mut operation: Op,
+    ) -> Retry<S, impl FnMut() -> F, AlwaysRetry>
+    where
+        G: FnOnce() -> String,
+        Op: FnMut(Arc<str>) -> F,
+    {
+        let key: Arc<str> = generate().into();
+        Retry::new(strategy, move || operation(Arc::clone(&key)))
+    }
+
+    /// Creates a new `Retry` instance whose operation and condition share
+    /// the same state.
+    ///
+    /// Like [`with_state()`](Retry::with_state), the operation receives its
+    /// own `Arc<T>` on each attempt. The condition receives `&T` alongside the
+    /// error, so a setting both of them depend on (e.g., a retry budget or a
+    /// threshold) lives in one place and can't drift out of sync.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError { status: u16 }
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn fetch(_: &str) -> Result<String, MyError> { Ok(String::new()) }
+    /// struct Config {
+    ///     url: String,
+    ///     retryable_statuses: Vec<u16>,
+    /// }
+    ///
+    /// # async fn example(config: Arc<Config>) {
+    /// let result = Retry::with_shared(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(3),
+    ///     config,
+    ///     |config| async move { fetch(&config.url).await },
+    ///     |config, error: &MyError| config.retryable_statuses.contains(&error.status),
+    /// )
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_shared<T, Op, F, Cond, E>(
+        strategy: S,
+        state: Arc<T>,
+        mut operation: Op,
+        mut condition: Cond,
+    ) -> Retry<S, impl FnMut() -> F, impl FnMut(&E) -> bool>
+    where
+        T: ?Sized,
+        Op: FnMut(Arc<T>) -> F,
+        Cond: FnMut(&T, &E) -> bool,
+    {
+        let condition_state = Arc::clone(&state);
+        Retry::new(strategy, move || operation(Arc::clone(&state)))
+            .with_condition(move |error: &E| condition(&condition_state, error))
+    }
+
+    /// Creates a new `Retry` instance that hands a mutable context to every
+    /// attempt.
+    ///
+    /// The context is owned by the retry and persists across attempts, so an
+    /// adaptive operation can record what it has tried (e.g., endpoints that
+    /// already failed) and change course. The operation mutates the context
+    /// synchronously and then returns a future, which must not borrow from it.
+    ///
+    /// Awaiting the returned `Retry` yields `(result, context)`, so the final
+    /// context can be inspected whether or not the operation succeeded. All
+    /// errors are retried, as with [`Retry::new()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn fetch_from(_: &str) -> Result<String, MyError> { Ok(String::new()) }
+    /// # async fn example() {
+    /// let endpoints = ["https://a.example.com", "https://b.example.com"];
+    ///
+    /// let (result, tried) = Retry::with_context(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(3),
+    ///     Vec::new(),
+    ///     move |tried: &mut Vec<&str>| {
+    ///         // Rotate through the endpoints, remembering each one tried
+    ///         let endpoint = endpoints[tried.len() % endpoints.len()];
+    ///         tried.push(endpoint);
+    ///         async move { fetch_from(endpoint).await }
+    ///     }
+    /// )
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_context<Ctx, Op, F>(
+        strategy: S,
+        context: Ctx,
+        operation: Op,
+    ) -> Retry<S, Contextual<Ctx, Op>, AlwaysRetry>
+    where
+        Op: FnMut(&mut Ctx) -> F,
+    {
+        Retry::new(strategy, Contextual { context, operation })
+    }
+}
+
 // Implementation block for builder methods, available on any Retry instance.
-impl<S, O, C> Retry<S, O, C>
+/// Methods that set the retry condition.
+///
+/// These are only available while no condition has been set, so a `Retry`
+/// ends up with exactly one of them; setting a second is a compile error.
+impl<S, O, K, B> Retry<S, O, AlwaysRetry, K, B>
 where
     S: Backoff,
 {
@@ -284,6 +852,30 @@ where
     /// By default, [`Retry::new()`] retries all errors. Use this method to specify
     /// custom logic for which errors are retryable.
     ///
+    /// The condition is consulted before the backoff strategy: an error it
+    /// rejects ends the loop without taking a delay from the strategy, so a
+    /// stateful strategy only advances for retries that actually happen.
+    ///
+    /// Only one condition can be set: this,
+    /// [`with_action_condition()`](Retry::with_action_condition) and
+    /// [`with_retryable_trait()`](Retry::with_retryable_trait) are unavailable
+    /// once any of them has been called, so conflicting predicates are
+    /// rejected at compile time.
+    ///
+    /// ```rust,compile_fail
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// let result = Retry::new(FixedDelay::new(Duration::from_secs(1)).take(3), || async {
+    ///     Err::<(), String>("failed".to_string())
+    /// })
+    /// .with_condition(|e: &String| e.contains("timeout"))
+    /// .with_condition(|e: &String| e.contains("refused")) // error: no such method
+    /// .await;
+    /// # }
+    /// ```
+    ///
     /// # Arguments
     ///
     /// * `condition` - A closure `Fn(&E) -> bool` that returns `true` for retryable errors
@@ -321,247 +913,2512 @@ where
     /// .await;
     /// # }
     /// ```
-    pub fn with_condition<NewC, E>(self, condition: NewC) -> Retry<S, O, NewC>
+    pub fn with_condition<NewC, E>(self, condition: NewC) -> Retry<S, O, NewC, K, B>
     where
         NewC: FnMut(&E) -> bool,
-        E: Error,
     {
         Retry {
             strategy: self.strategy,
             operation: self.operation,
-            condition,
-            max_duration: self.max_duration,
+            config: RetryConfig {
+                condition,
+                clock: self.config.clock,
+                circuit_breaker: self.config.circuit_breaker,
+                options: self.config.options,
+            },
         }
     }
 
-    /// Sets a maximum total duration for the entire retry operation.
-    ///
-    /// If the total time (including retries and delays) exceeds this
-    /// duration, the loop will stop and return the last error.
+    /// Sets a condition that decides both whether and when to retry.
     ///
-    /// # Arguments
-    ///
-    /// * `max_duration` - The maximum total time to spend retrying
+    /// Instead of a `bool`, `condition` returns a [`RetryAction`]:
     ///
-    /// # Behavior
+    /// - [`RetryAction::Retry`]: Retry after the strategy's next delay
+    /// - [`RetryAction::RetryAfter`]: Retry after the given delay instead
+    /// - [`RetryAction::Stop`]: Return the error without retrying
     ///
-    /// The retry loop checks the elapsed time:
-    /// 1. Before waiting for a backoff delay
-    /// 2. If the delay would cause the total time to exceed `max_duration`, the loop stops
+    /// The strategy still advances on `RetryAfter`, so its retry limit applies;
+    /// only its delay is replaced. `max_duration` is enforced against the
+    /// delay actually used.
     ///
     /// # Examples
     ///
-    /// Limit retries to 10 seconds total:
-    ///
     /// ```rust,no_run
-    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use async_retry::{Retry, RetryAction, backoff::ExponentialBackoff};
     /// use std::time::Duration;
     ///
     /// # #[derive(Debug, Clone)]
-    /// # struct MyError;
-    /// # impl std::fmt::Display for MyError {
+    /// # enum ApiError { RateLimited { retry_after: Duration }, Network, Auth }
+    /// # impl std::fmt::Display for ApiError {
     /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
     /// # }
-    /// # impl std::error::Error for MyError {}
-    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn call_api() -> Result<(), ApiError> { Ok(()) }
     /// # async fn example() {
-    /// // Even though the strategy allows many retries, this will stop after 10 seconds
     /// let result = Retry::new(
-    ///     FixedDelay::new(Duration::from_secs(1)),  // Infinite retries
-    ///     move || async move { operation().await }
+    ///     ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(5),
+    ///     move || async move { call_api().await }
     /// )
-    /// .with_max_duration(Duration::from_secs(10))  // But stop after 10 seconds
+    /// .with_action_condition(|e: &ApiError| match e {
+    ///     ApiError::RateLimited { retry_after } => RetryAction::RetryAfter(*retry_after),
+    ///     ApiError::Network => RetryAction::Retry,
+    ///     ApiError::Auth => RetryAction::Stop,
+    /// })
     /// .await;
     /// # }
     /// ```
-    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
-        self.max_duration = Some(max_duration);
-        self
+    pub fn with_action_condition<NewC, E>(
+        self,
+        condition: NewC,
+    ) -> Retry<S, O, ActionCondition<NewC>, K, B>
+    where
+        NewC: FnMut(&E) -> RetryAction,
+    {
+        Retry {
+            strategy: self.strategy,
+            operation: self.operation,
+            config: RetryConfig {
+                condition: ActionCondition(condition),
+                clock: self.config.clock,
+                circuit_breaker: self.config.circuit_breaker,
+                options: self.config.options,
+            },
+        }
     }
-}
-
-/// The core retry logic, implemented via `IntoFuture` for the default (always retry) condition.
-impl<S, O, F, T, E> IntoFuture for Retry<S, O, AlwaysRetry>
-where
-    S: Backoff + Send + 'static,
-    O: FnMut() -> F + Send + 'static,
-    F: Future<Output = Result<T, E>> + Send,
-    E: Error + Send,
-    T: Send,
-{
-    type Output = Result<T, E>;
-
-    // We box the future to avoid complex type signatures in the return.
-    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
-
-    /// Contains the core retry loop logic.
-    fn into_future(mut self) -> <Retry<S, O, AlwaysRetry> as IntoFuture>::IntoFuture {
-        Box::pin(async move {
-            let start_time = Instant::now();
-            let mut _attempt = 0;
-
-            loop {
-                _attempt += 1;
 
-                // Execute the async operation.
-                let result = (self.operation)().await;
+    /// Retries only errors whose [`Retryable::is_retryable()`] returns `true`.
+    ///
+    /// This is an alternative to [`with_condition()`](Retry::with_condition) for
+    /// error types that already know whether they are transient.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, Retryable, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # enum ApiError {
+    /// #     Network,
+    /// #     Auth,
+    /// # }
+    /// # impl std::fmt::Display for ApiError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # impl std::error::Error for ApiError {}
+    /// impl Retryable for ApiError {
+    ///     fn is_retryable(&self) -> bool {
+    ///         matches!(self, ApiError::Network)
+    ///     }
+    /// }
+    ///
+    /// # async fn call_api() -> Result<(), ApiError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(3),
+    ///     move || async move { call_api().await }
+    /// )
+    /// .with_retryable_trait()
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_retryable_trait(self) -> Retry<S, O, RetryableCondition, K, B> {
+        Retry {
+            strategy: self.strategy,
+            operation: self.operation,
+            config: RetryConfig {
+                condition: RetryableCondition,
+                clock: self.config.clock,
+                circuit_breaker: self.config.circuit_breaker,
+                options: self.config.options,
+            },
+        }
+    }
+}
+
+impl<S, O, C, K, B> Retry<S, O, C, K, B>
+where
+    S: Backoff,
+{
+    /// Turns retrying on or off.
+    ///
+    /// With `false`, the operation runs exactly once and its result is
+    /// returned as-is, whatever the strategy and condition say. This is
+    /// simpler than building a different strategy when retrying is disabled
+    /// by configuration. Retrying is enabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example(retries_enabled: bool) {
+    /// let result = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(5),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_enabled(retries_enabled)
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.config.options.retries_disabled = !enabled;
+        self
+    }
+
+    /// Checks that the strategy allows at least one retry.
+    ///
+    /// Returns [`BackoffError::NoRetries`] if the strategy reports that it
+    /// will yield no delays, e.g. `FixedDelay::new(d).take(0)` or
+    /// `with_max_retries(0)`, where the operation would run once and never be
+    /// retried. The check relies on the strategy's
+    /// [`size_hint()`](Iterator::size_hint): a custom strategy that doesn't
+    /// report an upper bound always passes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::{Retry, backoff::{BackoffError, FixedDelay}};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// let retries = 0; // e.g., read from configuration
+    /// let retry = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(retries),
+    ///     move || async move { operation().await }
+    /// )
+    /// .at_least_once();
+    ///
+    /// assert_eq!(retry.err(), Some(BackoffError::NoRetries));
+    /// ```
+    pub fn at_least_once(self) -> Result<Self, BackoffError> {
+        if self.strategy.size_hint().1 == Some(0) {
+            return Err(BackoffError::NoRetries);
+        }
+        Ok(self)
+    }
+
+    /// Sets a maximum total duration for the entire retry operation.
+    ///
+    /// If the total time (including retries and delays) exceeds this
+    /// duration, the loop will stop and return the last error.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_duration` - The maximum total time to spend retrying
+    ///
+    /// # Behavior
+    ///
+    /// The retry loop checks the elapsed time:
+    /// 1. Before waiting for a backoff delay
+    /// 2. If the delay would cause the total time to exceed `max_duration`, the loop stops
+    ///
+    /// A delay ending exactly at `max_duration` is still slept, and the attempt
+    /// made then still runs.
+    ///
+    /// The first attempt always runs, however small `max_duration` is. If the
+    /// first delay doesn't fit in what is left of it (e.g., a 50ms maximum
+    /// with a 100ms first delay), the loop returns the first attempt's error
+    /// without retrying at all. Use
+    /// [`with_min_one_retry()`](Retry::with_min_one_retry) to shorten that
+    /// delay and retry once instead. Once the budget is fully spent, as when
+    /// the first attempt alone takes longer than `max_duration`, no retry is
+    /// made either way.
+    ///
+    /// # Interaction with Retry Limits
+    ///
+    /// When the strategy also limits retries (e.g., with `with_max_retries()`),
+    /// whichever limit is reached first stops the loop. After each failed
+    /// attempt, the limits are checked in this order:
+    ///
+    /// 1. The elapsed time has reached `max_duration`: stop on the duration.
+    /// 2. The strategy is exhausted: stop on the retry limit.
+    /// 3. The next delay would end past `max_duration`: stop on the duration.
+    ///
+    /// So if both limits are reached by the same attempt, the duration wins.
+    /// This only matters with [`with_timeout_error()`](Retry::with_timeout_error),
+    /// which maps the last error when, and only when, the duration stopped the
+    /// loop. An error the condition doesn't retry stops the loop before any
+    /// limit is checked, so it is never mapped.
+    ///
+    /// # Examples
+    ///
+    /// Limit retries to 10 seconds total:
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # impl std::error::Error for MyError {}
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// // Even though the strategy allows many retries, this will stop after 10 seconds
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)),  // Infinite retries
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_max_duration(Duration::from_secs(10))  // But stop after 10 seconds
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.config.options.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Guarantees a retry within the maximum duration while budget remains.
+    ///
+    /// Normally a delay that would end past the
+    /// [`with_max_duration()`](Retry::with_max_duration) limit stops the loop.
+    /// With this set, if the first attempt fails and its delay doesn't fit,
+    /// the delay is shortened to the time left and a second attempt is made.
+    /// Later delays are not shortened. If no time is left at all, the loop
+    /// stops as usual. Without a maximum duration this has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// // The first delay is 1s, but a second attempt still happens within 300ms
+    /// let result = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_secs(1)),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_max_duration(Duration::from_millis(300))
+    /// .with_min_one_retry()
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_min_one_retry(mut self) -> Self {
+        self.config.options.min_one_retry = true;
+        self
+    }
+
+    /// Sets a maximum total duration that also bounds each attempt.
+    ///
+    /// [`with_max_duration()`](Retry::with_max_duration) only stops between
+    /// attempts, so a slow attempt can run past the budget. This also races
+    /// every attempt against the remaining budget: an attempt still running
+    /// when the budget is spent is dropped and fails with a [`RetryTimeout`],
+    /// converted into the operation's error type with `From<RetryTimeout>`,
+    /// and the loop then stops as it would for `with_max_duration()`.
+    ///
+    /// The budget is measured and waited on with the [`Clock`], like the
+    /// maximum duration itself, and it starts afresh on every run, so a
+    /// `Retry` reused with [`run_with()`](Retry::run_with) gets the full budget each
+    /// time. The clock's timer only starts once an attempt has to wait, so
+    /// with a `TestClock`, whose sleeps finish at once, an attempt that is
+    /// still pending when first polled uses up the rest of the budget.
+    ///
+    /// This wraps the current condition, so call it after
+    /// [`with_condition()`](Retry::with_condition) or
+    /// [`with_retryable_trait()`](Retry::with_retryable_trait).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, RetryTimeout, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug)]
+    /// # enum MyError { TimedOut(RetryTimeout) }
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// impl From<RetryTimeout> for MyError {
+    ///     fn from(e: RetryTimeout) -> Self {
+    ///         MyError::TimedOut(e)
+    ///     }
+    /// }
+    ///
+    /// # async fn slow_call() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// // Returns within about 2 seconds, however slow each call is
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)),
+    ///     move || async move { slow_call().await }
+    /// )
+    /// .with_strict_max_duration(Duration::from_secs(2))
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_strict_max_duration(
+        mut self,
+        max_duration: Duration,
+    ) -> Retry<S, O, StrictMaxDuration<C>, K, B> {
+        self.config.options.max_duration = Some(max_duration);
+        Retry {
+            strategy: self.strategy,
+            operation: self.operation,
+            config: RetryConfig {
+                condition: StrictMaxDuration {
+                    condition: self.config.condition,
+                    max_duration,
+                },
+                clock: self.config.clock,
+                circuit_breaker: self.config.circuit_breaker,
+                options: self.config.options,
+            },
+        }
+    }
+
+    /// Sets the maximum total duration to a fraction of the time remaining
+    /// before `parent_deadline`.
+    ///
+    /// This is for request-scoped budgets: with a deadline 1 second away and a
+    /// `fraction` of `0.5`, it is equivalent to
+    /// [`with_max_duration()`](Retry::with_max_duration) with 500ms. The
+    /// remaining time is measured once, when this method is called, using
+    /// the configured [`Clock`], so call it after
+    /// [`with_clock()`](Retry::with_clock). A deadline that has already
+    /// passed gives a maximum duration of zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` is not between `0.0` and `1.0` (inclusive).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # impl std::error::Error for MyError {}
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example(request_deadline: Instant) {
+    /// // Spend at most half of the request's remaining budget on this call
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_duration_fraction(request_deadline, 0.5)
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_duration_fraction(mut self, parent_deadline: Instant, fraction: f64) -> Self
+    where
+        K: Clock,
+    {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "duration fraction must be between 0.0 and 1.0, got {}",
+            fraction
+        );
+        let remaining = parent_deadline.saturating_duration_since(self.config.clock.now());
+        self.config.options.max_duration = Some(remaining.mul_f64(fraction));
+        self
+    }
+
+    /// Enables hedged attempts to reduce tail latency.
+    ///
+    /// Instead of waiting for an attempt to fail, if it has not completed after
+    /// `hedge_delay`, a second invocation of the operation is started and raced
+    /// against the first. The first invocation to succeed wins and the other is
+    /// dropped. If the first to finish fails, the other is awaited instead.
+    /// A hedged pair counts as a single attempt for retry and backoff purposes.
+    ///
+    /// **The operation must be safe to run concurrently with itself**, since
+    /// two invocations may be in flight at once. Only use hedging for
+    /// idempotent operations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # impl std::error::Error for MyError {}
+    /// # async fn read_replica() -> Result<String, MyError> { Ok(String::new()) }
+    /// # async fn example() {
+    /// // If a read takes longer than 50ms, fire a second one and take the faster
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(3),
+    ///     move || async move { read_replica().await }
+    /// )
+    /// .with_hedging(Duration::from_millis(50))
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_hedging(mut self, hedge_delay: Duration) -> Self {
+        self.config.options.hedge_delay = Some(hedge_delay);
+        self
+    }
+
+    /// Catches panics in the operation and treats them as errors.
+    ///
+    /// Each invocation, including the call that creates the future, is run
+    /// under [`std::panic::catch_unwind`]. A caught panic becomes a
+    /// [`PanicError`], converted into the operation's error type with
+    /// `From<PanicError>`, and then goes through the retry condition like any
+    /// other error.
+    ///
+    /// **Unwind safety is asserted, not checked.** Any state the operation
+    /// shares with the caller (captured `Arc<Mutex<_>>`s, counters, caches)
+    /// may be left half-updated by the panic, and the next attempt will see
+    /// it. Only use this when the operation can cope with that. Panics are
+    /// still reported by the panic hook (printed to stderr by default), and
+    /// nothing is caught when the crate is built with `panic = "abort"`.
+    ///
+    /// This wraps the operation passed to [`new()`](Retry::new), so a `Retry`
+    /// using it can't be reused with [`run_with()`](Retry::run_with), which would
+    /// otherwise drive its own operations without catching their panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{PanicError, Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug)]
+    /// # enum MyError { Panicked(PanicError) }
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// impl From<PanicError> for MyError {
+    ///     fn from(e: PanicError) -> Self {
+    ///         MyError::Panicked(e)
+    ///     }
+    /// }
+    ///
+    /// # async fn flaky_client_call() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(3),
+    ///     move || async move { flaky_client_call().await }
+    /// )
+    /// .with_catch_unwind()
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_catch_unwind<F, T, E>(self) -> Retry<S, impl FnMut() -> CatchUnwind<F>, C, K, B>
+    where
+        O: FnMut() -> F,
+        F: Future<Output = Result<T, E>>,
+        E: From<PanicError>,
+    {
+        let mut operation = self.operation;
+        Retry {
+            strategy: self.strategy,
+            operation: move || unwind::call(&mut operation),
+            config: self.config,
+        }
+    }
+
+    /// Runs every attempt of the operation inside a `tracing` span.
+    ///
+    /// The span is entered while the operation is called and while its future
+    /// is polled, so events emitted inside the operation are recorded under
+    /// it. This keeps the caller's context across spawned retries, where the
+    /// current span would otherwise be lost. The span also applies to the
+    /// operations passed to [`run_with()`](Retry::run_with). It requires the `tracing`
+    /// feature flag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn charge(order_id: u64) -> Result<(), MyError> { Ok(()) }
+    /// # async fn example(order_id: u64) {
+    /// let span = tracing::info_span!("charge", order_id);
+    ///
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(3),
+    ///     move || async move { charge(order_id).await }
+    /// )
+    /// .with_span(span)
+    /// .await;
+    /// # }
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn with_span(mut self, span: tracing::Span) -> Self {
+        self.config.options.span = Some(span);
+        self
+    }
+
+    /// Sets a hook that runs immediately before every invocation of the operation.
+    ///
+    /// The hook receives the attempt number, starting at `1` for the initial
+    /// attempt. This is useful for per-attempt side effects such as refreshing
+    /// an auth token or logging a correlation id.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(3),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_before_attempt(|attempt| println!("Starting attempt {}", attempt))
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_before_attempt<H>(mut self, hook: H) -> Self
+    where
+        H: FnMut(usize) + Send + 'static,
+    {
+        self.config.options.before_attempt = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets a final adjustment applied to every delay produced by the strategy.
+    ///
+    /// `transform` receives the strategy's delay and the number of the attempt
+    /// that just failed (starting at `1`), and returns the delay to sleep
+    /// instead. It runs after wrappers such as jitter and caps inside the
+    /// strategy, so it sees the delay the loop would otherwise use, and before
+    /// the [`with_max_duration()`](Retry::with_max_duration) check. Delays
+    /// requested by the condition with [`RetryAction::RetryAfter`] are used
+    /// as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// // Allow for a 20ms round trip on top of every backoff delay
+    /// let result = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(5),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_delay_transform(|delay, _attempt| delay + Duration::from_millis(20))
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_delay_transform<D>(mut self, transform: D) -> Self
+    where
+        D: FnMut(Duration, usize) -> Duration + Send + 'static,
+    {
+        self.config.options.delay_transform = Some(Box::new(transform));
+        self
+    }
+
+    /// Waits at least as long as the failed attempt took before retrying.
+    ///
+    /// Each backoff delay is raised to the measured latency of the attempt
+    /// that just failed, so a dependency that slows down under load is given
+    /// proportionally more room to recover. The floor applies after
+    /// [`with_delay_transform()`](Retry::with_delay_transform); delays
+    /// requested by the condition with [`RetryAction::RetryAfter`] are used
+    /// as-is. Latency is measured with the configured [`Clock`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// // If an attempt takes 2s to fail, wait at least 2s before the next one
+    /// let result = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(5),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_latency_aware_delay()
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_latency_aware_delay(mut self) -> Self {
+        self.config.options.latency_aware_delay = true;
+        self
+    }
+
+    /// Delays the first attempt by a random duration in `0..=splay`.
+    ///
+    /// When a fleet of processes restarts at once (e.g., during a deploy),
+    /// their first attempts and therefore their whole backoff sequences line
+    /// up. A one-off random delay before the first attempt spreads them out.
+    /// It is separate from the backoff strategy, which still controls the
+    /// delays between attempts. The splay counts toward
+    /// [`with_max_duration()`](Retry::with_max_duration), and is drawn from
+    /// [`ThreadRngSource`] unless another source is set with
+    /// [`with_splay_source()`](Retry::with_splay_source). It requires the
+    /// `jitter` feature flag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn connect() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(5),
+    ///     move || async move { connect().await }
+    /// )
+    /// .with_startup_splay(Duration::from_secs(2))
+    /// .await;
+    /// # }
+    /// ```
+    #[cfg(feature = "jitter")]
+    pub fn with_startup_splay(mut self, splay: Duration) -> Self {
+        self.config.options.startup_splay = Some(splay);
+        self
+    }
+
+    /// Draws the startup splay from `source`.
+    ///
+    /// The default is [`ThreadRngSource`]. A [`SeededSource`] makes the
+    /// splay reproducible, e.g. in tests. It has no effect without
+    /// [`with_startup_splay()`](Retry::with_startup_splay), and requires the
+    /// `jitter` feature flag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, SeededSource, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn connect() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(5),
+    ///     move || async move { connect().await }
+    /// )
+    /// .with_startup_splay(Duration::from_secs(2))
+    /// .with_splay_source(SeededSource::new(42))
+    /// .await;
+    /// # }
+    /// ```
+    #[cfg(feature = "jitter")]
+    pub fn with_splay_source<R>(mut self, source: R) -> Self
+    where
+        R: JitterSource + Send + 'static,
+    {
+        self.config.options.splay_source = Some(Box::new(source));
+        self
+    }
+
+    /// Stops retrying once a shared flag is set, e.g. during shutdown.
+    ///
+    /// The flag is checked after every failed attempt and while sleeping
+    /// between attempts. When it is set, the loop returns the last error
+    /// instead of retrying. The first attempt always runs, and an attempt
+    /// already in progress is not interrupted.
+    ///
+    /// An `AtomicBool` can't wake a sleeping task, so while the flag is
+    /// configured, backoff delays are slept in slices of at most 10ms and the
+    /// flag is polled between them. An abort therefore takes effect within
+    /// about 10ms, even in the middle of a long delay.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn sync_state() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let shutting_down = Arc::new(AtomicBool::new(false));
+    ///
+    /// let retry = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(30)),
+    ///     move || async move { sync_state().await }
+    /// )
+    /// .with_abort_flag(shutting_down.clone());
+    ///
+    /// // Elsewhere, on shutdown: `shutting_down.store(true, Ordering::Relaxed)`
+    /// # shutting_down.store(true, Ordering::Relaxed);
+    /// let result = retry.await;
+    /// # }
+    /// ```
+    pub fn with_abort_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.config.options.abort_flag = Some(flag);
+        self
+    }
+
+    /// Cuts a backoff sleep short when `signal` resolves.
+    ///
+    /// Each backoff sleep is raced against `signal`. If the signal resolves
+    /// first, the rest of the delay is skipped and the next attempt starts
+    /// immediately, which suits a shared readiness notification (e.g., a
+    /// "connection restored" event) that makes waiting pointless. The signal
+    /// fires only once: later sleeps run their full delay as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn connect() -> Result<(), MyError> { Ok(()) }
+    /// # async fn network_up() {}
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(30)).take(10),
+    ///     move || async move { connect().await }
+    /// )
+    /// .with_wakeup_signal(network_up())
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_wakeup_signal<W>(mut self, signal: W) -> Self
+    where
+        W: Future + Send + 'static,
+    {
+        self.config.options.wakeup_signal = Some(Box::pin(async move {
+            signal.await;
+        }));
+        self
+    }
+
+    /// Publishes the current attempt number to a shared counter.
+    ///
+    /// The counter is set at the start of every attempt (`1` for the first),
+    /// so another task can read it while the retry runs, e.g. to update a
+    /// progress display, without needing a callback.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn upload() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let counter = Arc::new(AtomicUsize::new(0));
+    ///
+    /// let retry = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(5),
+    ///     move || async move { upload().await }
+    /// )
+    /// .with_attempt_counter(counter.clone());
+    ///
+    /// // Elsewhere: `counter.load(Ordering::Relaxed)` is the attempt in progress
+    /// # let _ = counter.load(Ordering::Relaxed);
+    /// retry.await;
+    /// # }
+    /// ```
+    pub fn with_attempt_counter(mut self, counter: Arc<AtomicUsize>) -> Self {
+        self.config.options.attempt_counter = Some(counter);
+        self
+    }
+
+    /// Publishes the run's live progress to a [`RetryHandle`].
+    ///
+    /// The handle is updated at the start of every attempt and after every
+    /// failed one, so a clone kept elsewhere can report
+    /// [`attempts_made()`](RetryHandle::attempts_made) and
+    /// [`last_error()`](RetryHandle::last_error) while the retry runs. For a
+    /// spawned retry, [`spawn_with_handle()`](Retry::spawn_with_handle) does
+    /// this for you.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, RetryHandle, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn connect() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let handle = RetryHandle::new();
+    ///
+    /// let retry = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(30),
+    ///     move || async move { connect().await }
+    /// )
+    /// .with_handle(handle.clone());
+    ///
+    /// // Elsewhere: report `handle.attempts_made()` and `handle.last_error()`
+    /// retry.await;
+    /// # }
+    /// ```
+    pub fn with_handle(mut self, handle: RetryHandle) -> Self {
+        self.config.options.handle = Some(handle);
+        self
+    }
+
+    /// Publishes every delay between attempts to a Tokio `watch` channel.
+    ///
+    /// Just before each sleep, `sender` is sent a [`Countdown`] with the
+    /// delay and the instant it started, so a subscriber can render e.g.
+    /// "Retrying in 3... 2... 1...". The channel starts at `None` and keeps
+    /// the last countdown once the retry finishes. The value is stored even
+    /// when there are no receivers, so one that subscribes later still sees
+    /// the current countdown. It requires the `tokio-timer` feature flag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Countdown, Retry, backoff::FixedDelay};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn connect() -> Result<(), MyError> { Ok(()) }
+    /// # fn render(_: &str) {}
+    /// # async fn example() {
+    /// let (tx, mut rx) = tokio::sync::watch::channel::<Option<Countdown>>(None);
+    ///
+    /// tokio::spawn(async move {
+    ///     while rx.changed().await.is_ok() {
+    ///         if let Some(countdown) = *rx.borrow_and_update() {
+    ///             let secs = countdown.remaining_at(Instant::now()).as_secs();
+    ///             render(&format!("Retrying in {}...", secs));
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(3)).take(5),
+    ///     move || async move { connect().await }
+    /// )
+    /// .with_countdown_channel(tx)
+    /// .await;
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio-timer")]
+    pub fn with_countdown_channel(
+        mut self,
+        sender: tokio::sync::watch::Sender<Option<Countdown>>,
+    ) -> Self {
+        self.config.options.countdown = Some(sender);
+        self
+    }
+
+    /// Sets a name for the operation, attached to every log record it emits.
+    ///
+    /// With the `logging` feature, each retry event is logged with structured
+    /// key-value fields via the `log` crate's key-value API:
+    ///
+    /// - `operation_name`: The name set here, if any
+    /// - `attempt`: The attempt number, starting at `1`
+    /// - `error_display`: The failed attempt's error, formatted with `Display`
+    /// - `delay_ms`: The backoff delay before the next attempt, in milliseconds
+    /// - `max_duration_ms`: The configured maximum duration, when it is exceeded
+    ///
+    /// Loggers that support key-values (e.g., JSON loggers) can emit these as
+    /// fields; others only print the usual message. Without the `logging`
+    /// feature, the name is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn fetch_user() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(3),
+    ///     move || async move { fetch_user().await }
+    /// )
+    /// .with_operation_name("fetch_user")
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_operation_name(mut self, name: impl Into<String>) -> Self {
+        self.config.options.operation_name = Some(name.into());
+        self
+    }
+
+    /// Sets the level used to log each failed attempt.
+    ///
+    /// Failed attempts (and rejected values, with
+    /// [`with_validator()`](Retry::with_validator)) are logged at
+    /// [`Level::Warn`](log::Level::Warn) by default. Where retries are expected,
+    /// a lower level keeps them out of warning dashboards. The final outcome
+    /// is still logged at its own level, e.g. `error` when the loop gives up.
+    /// It requires the `logging` feature flag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn poll_queue() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(10),
+    ///     move || async move { poll_queue().await }
+    /// )
+    /// .with_log_level(log::Level::Debug)
+    /// .await;
+    /// # }
+    /// ```
+    #[cfg(feature = "logging")]
+    pub fn with_log_level(mut self, level: log::Level) -> Self {
+        self.config.options.log_level = Some(level);
+        self
+    }
+
+    /// Flags successes that needed more than `threshold` attempts as degraded.
+    ///
+    /// A dependency that keeps succeeding only after several retries is
+    /// working but flaky, which is worth alerting on before it fails outright.
+    /// When the successful attempt number exceeds `threshold`, a warning is
+    /// logged (with the `logging` feature; its `degraded_threshold` field
+    /// marks it) and [`RetryStats::degraded`] is set for
+    /// [`run_with_stats()`](Retry::run_with_stats).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let (result, stats) = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(5),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_degraded_threshold(2)
+    /// .run_with_stats()
+    /// .await;
+    ///
+    /// if stats.degraded {
+    ///     eprintln!("succeeded, but only after {} attempts", stats.attempts);
+    /// }
+    /// # }
+    /// ```
+    pub fn with_degraded_threshold(mut self, threshold: usize) -> Self {
+        self.config.options.degraded_threshold = Some(threshold);
+        self
+    }
+
+    /// Stops retrying after `max_zero_delays` consecutive zero delays.
+    ///
+    /// This is a guard against a footgun rather than a retry policy: a custom
+    /// strategy that always yields [`Duration::ZERO`] and never ends, without
+    /// a [`with_max_duration()`](Retry::with_max_duration), retries a failing
+    /// operation forever as fast as it can. With the guard, the loop gives up
+    /// with the last error once that many retries in a row had no delay. Any
+    /// non-zero delay resets the count.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::Retry;
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// // An infinite zero-delay strategy, stopped after 100 immediate retries
+    /// let result = Retry::new(
+    ///     std::iter::repeat(Duration::ZERO),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_spin_guard(100)
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_spin_guard(mut self, max_zero_delays: usize) -> Self {
+        self.config.options.spin_guard = Some(max_zero_delays);
+        self
+    }
+
+    /// Limits the total number of attempts, counting the first one.
+    ///
+    /// Strategy limits such as `with_max_retries(3)` count only retries, so
+    /// the operation runs up to 4 times. With `with_max_attempts(3)` it runs
+    /// at most 3 times in total, whatever the strategy, which is often the
+    /// more natural reading. The loop stops at whichever limit is reached
+    /// first, returning the last error. The first attempt always runs, so a
+    /// `max_attempts` of `0` behaves like `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn fetch() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// // 3 attempts in total: the first, then 2 retries
+    /// let result = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_millis(100)),
+    ///     move || async move { fetch().await }
+    /// )
+    /// .with_max_attempts(3)
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.config.options.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Records the delays actually slept between attempts.
+    ///
+    /// The recorded delays are returned in [`RetryStats::delays`] by
+    /// [`run_with_stats()`](Retry::run_with_stats). Unlike the strategy's nominal
+    /// schedule, they include any jitter, and omit a final delay that was never
+    /// slept because the loop gave up. This is useful for correlating a failed
+    /// retry sequence with an incident timeline.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn fetch() -> Result<String, MyError> { Ok(String::new()) }
+    /// # async fn example() {
+    /// let (result, stats) = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_millis(100)).take(5),
+    ///     move || async move { fetch().await }
+    /// )
+    /// .with_record_delays()
+    /// .run_with_stats()
+    /// .await;
+    ///
+    /// if result.is_err() {
+    ///     eprintln!("Gave up after sleeping {:?}", stats.delays.unwrap_or_default());
+    /// }
+    /// # }
+    /// ```
+    pub fn with_record_delays(mut self) -> Self {
+        self.config.options.record_delays = true;
+        self
+    }
+
+    /// Retries `Ok` values that `validator` rejects, as if the attempt had failed.
+    ///
+    /// Some values are structurally valid but semantically wrong, such as an
+    /// empty response body when data was expected. When `validator` returns
+    /// `false` for a value, the loop backs off and tries again, consuming the
+    /// strategy and time budget like a retryable error would.
+    ///
+    /// Rejected values never reach the retry condition: the condition only
+    /// ever sees errors, so it decides whether *errors* are retried, while
+    /// invalid values are always retried. If the loop gives up (the strategy
+    /// is exhausted, the maximum duration runs out, or it is aborted) after a
+    /// rejected value, it returns the last error an attempt failed with, or,
+    /// if every attempt returned a value, an [`InvalidValue`] converted into
+    /// the operation's error type with `From<InvalidValue>`. A rejected value
+    /// is never returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # impl From<async_retry::InvalidValue> for MyError {
+    /// #     fn from(_: async_retry::InvalidValue) -> Self { MyError }
+    /// # }
+    /// # async fn fetch_items() -> Result<Vec<String>, MyError> { Ok(Vec::new()) }
+    /// # async fn example() {
+    /// // Retry until the listing is non-empty, or errors or retries run out
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(200)).take(5),
+    ///     move || async move { fetch_items().await }
+    /// )
+    /// .with_validator(|items: &Vec<String>| !items.is_empty())
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_validator<V>(self, validator: V) -> Retry<S, Validated<O, V>, C, K, B> {
+        Retry {
+            strategy: self.strategy,
+            operation: Validated {
+                operation: self.operation,
+                validator,
+            },
+            config: self.config,
+        }
+    }
+
+    /// Maps the last error when the retry loop gives up because of the
+    /// maximum duration set by [`with_max_duration()`](Retry::with_max_duration).
+    ///
+    /// By default, running out of time returns the last operation error, which
+    /// can be misleading (e.g., "connection refused" when the real problem is
+    /// that the deadline passed). `map` receives that error and returns the one
+    /// to report instead. It is not called when the loop stops for any other
+    /// reason.
+    ///
+    /// This wraps the current condition, so call it after
+    /// [`with_condition()`](Retry::with_condition) or
+    /// [`with_retryable_trait()`](Retry::with_retryable_trait).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug)]
+    /// # enum ApiError { Network(String), DeadlineExceeded { last: Box<ApiError> } }
+    /// # impl std::fmt::Display for ApiError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn call_api() -> Result<(), ApiError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)),
+    ///     move || async move { call_api().await }
+    /// )
+    /// .with_max_duration(Duration::from_secs(10))
+    /// .with_timeout_error(|last| ApiError::DeadlineExceeded { last: Box::new(last) })
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_timeout_error<M, E>(self, map: M) -> Retry<S, O, MapTimeoutError<C, M>, K, B>
+    where
+        M: FnMut(E) -> E,
+    {
+        Retry {
+            strategy: self.strategy,
+            operation: self.operation,
+            config: RetryConfig {
+                condition: MapTimeoutError {
+                    condition: self.config.condition,
+                    map,
+                },
+                clock: self.config.clock,
+                circuit_breaker: self.config.circuit_breaker,
+                options: self.config.options,
+            },
+        }
+    }
+
+    /// Replaces the [`Clock`] used to measure elapsed time and to sleep between attempts.
+    ///
+    /// This is mainly useful for tests: with the `test-util` feature, a
+    /// `TestClock` lets you drive a full backoff schedule without waiting on
+    /// the wall clock.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "test-util")]
+    /// # mod example {
+    /// use async_retry::{Retry, TestClock, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # impl std::error::Error for MyError {}
+    /// # async fn operation() -> Result<(), MyError> { Err(MyError) }
+    /// # async fn example() {
+    /// let clock = TestClock::new();
+    /// let result = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(5),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_clock(clock.clone())
+    /// .await;
+    ///
+    /// // 1s + 2s + 4s + 8s + 16s of virtual time, in no real time at all
+    /// assert_eq!(clock.elapsed(), Duration::from_secs(31));
+    /// # }
+    /// # }
+    /// ```
+    pub fn with_clock<NewK: Clock>(self, clock: NewK) -> Retry<S, O, C, NewK, B> {
+        Retry {
+            strategy: self.strategy,
+            operation: self.operation,
+            config: RetryConfig {
+                condition: self.config.condition,
+                clock,
+                circuit_breaker: self.config.circuit_breaker,
+                options: self.config.options,
+            },
+        }
+    }
+
+    /// Replaces how the retry sleeps between attempts.
+    ///
+    /// Every backoff delay is passed to `sleep`, and the returned future is
+    /// awaited instead of the timer selected by the feature flags. The current
+    /// time is still read from the configured [`Clock`]. This lets exotic
+    /// runtimes supply their own timer, and lets tests record the requested
+    /// delays without waiting. For full control over time as well, implement
+    /// [`Clock`] and use [`with_clock()`](Retry::with_clock).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Err(MyError) }
+    /// # async fn example() {
+    /// let delays = Arc::new(Mutex::new(Vec::new()));
+    /// let recorded = delays.clone();
+    ///
+    /// let result = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(3),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_sleep_fn(move |delay| {
+    ///     recorded.lock().unwrap().push(delay);
+    ///     std::future::ready(())
+    /// })
+    /// .await;
+    ///
+    /// // Returns immediately, having "slept" 1s, 2s and 4s
+    /// # }
+    /// ```
+    pub fn with_sleep_fn<F, Fut>(self, sleep: F) -> Retry<S, O, C, SleepFn<K, F>, B>
+    where
+        F: Fn(Duration) -> Fut,
+        Fut: Future<Output = ()> + Send,
+    {
+        Retry {
+            strategy: self.strategy,
+            operation: self.operation,
+            config: RetryConfig {
+                condition: self.config.condition,
+                clock: SleepFn::new(self.config.clock, sleep),
+                circuit_breaker: self.config.circuit_breaker,
+                options: self.config.options,
+            },
+        }
+    }
+
+    /// Connects this retry to a shared [`CircuitBreaker`].
+    ///
+    /// The circuit is checked before every attempt. While it is open, the
+    /// retry fails fast with the operation's error type converted from
+    /// [`CircuitOpenError`](circuit::CircuitOpenError), without calling the
+    /// operation. Every attempt's success or failure is recorded on the breaker,
+    /// so repeated failures across all operations sharing it will open it. A
+    /// value rejected by [`with_validator()`](Retry::with_validator) counts as
+    /// a failure.
+    ///
+    /// The operation's error type must implement `From<CircuitOpenError>`.
+    /// See the [`circuit`] module for an example.
+    pub fn with_circuit_breaker(
+        self,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Retry<S, O, C, K, Arc<CircuitBreaker>> {
+        Retry {
+            strategy: self.strategy,
+            operation: self.operation,
+            config: RetryConfig {
+                condition: self.config.condition,
+                clock: self.config.clock,
+                circuit_breaker,
+                options: self.config.options,
+            },
+        }
+    }
+}
+
+// Implementation block for reusing a configured Retry across operations.
+impl<S, C, K, B> Retry<S, (), C, K, B>
+where
+    S: Backoff + Clone,
+    K: Clock,
+{
+    /// Runs `operation` using this `Retry`'s configuration, without consuming it.
+    ///
+    /// This lets one configured instance drive several operations in sequence.
+    /// It is only available on a `Retry` created with `()` as its operation.
+    ///
+    /// Settings that wrap the operation given to `new()`, such as
+    /// [`with_validator()`](Retry::with_validator) or
+    /// [`with_catch_unwind()`](Retry::with_catch_unwind), change the
+    /// operation's type, so a `Retry` using them has no `run_with()` at all.
+    /// This turns what would otherwise be a setting silently ignored by
+    /// `run_with()` into a compile error. Do the equivalent inside each
+    /// operation passed to `run_with()` instead.
+    ///
+    /// # Reset Semantics
+    ///
+    /// Each run drives a fresh clone of the strategy as it was configured, so
+    /// the backoff always starts from its first delay. Everything else is
+    /// shared between runs: condition closures and hooks keep their internal
+    /// state, and a circuit breaker keeps recording outcomes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn fetch(_: u32) -> Result<String, MyError> { Ok(String::new()) }
+    /// # async fn example() {
+    /// let mut retry = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(3),
+    ///     (),
+    /// );
+    ///
+    /// for id in 0..10 {
+    ///     // Every run starts again from a 100ms delay
+    ///     let result = retry.run_with(move || async move { fetch(id).await }).await;
+    /// }
+    /// # }
+    /// ```
+    pub async fn run_with<Op, F, T, E>(&mut self, mut operation: Op) -> Result<T, E>
+    where
+        Op: FnMut() -> F,
+        F: Future<Output = Result<T, E>>,
+        C: RetryCondition<E>,
+        B: CircuitGuard<E>,
+        E: Display,
+    {
+        // Drive a clone so the configured strategy starts fresh on every run.
+        let mut strategy = self.strategy.clone();
+        self.config
+            .execute(&mut strategy, &mut operation, &mut |_: &T| true)
+            .await
+            .0
+    }
+}
+
+// Implementation block for executing the retry loop.
+//
+// These methods carry no `Send` bounds: the returned futures are `Send`
+// exactly when the operation, condition, strategy, and clock are.
+impl<S, O, C, K, B, F, T, E> Retry<S, O, C, K, B>
+where
+    S: Backoff,
+    K: Clock,
+    B: CircuitGuard<E>,
+    O: FnMut() -> F,
+    C: RetryCondition<E>,
+    F: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    /// Runs the retry loop to completion, consuming the `Retry`.
+    ///
+    /// This is exactly what `.await`ing the `Retry` does, spelled out as a
+    /// method call so the intent to run it is visible at the call site.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let calls = Arc::new(AtomicU32::new(0));
+    /// let counter = calls.clone();
+    ///
+    /// let result = Retry::new(FixedDelay::new(Duration::from_millis(1)).take(3), move || {
+    ///     let counter = counter.clone();
+    ///     async move {
+    ///         match counter.fetch_add(1, Ordering::SeqCst) {
+    ///             0 => Err("not yet"),
+    ///             n => Ok(n + 1),
+    ///         }
+    ///     }
+    /// })
+    /// .run()
+    /// .await;
+    ///
+    /// assert_eq!(result, Ok(2));
+    /// # }
+    /// ```
+    pub async fn run(self) -> Result<T, E> {
+        self.execute().await.0
+    }
+
+    /// Runs the retry loop, returning the value together with the number of
+    /// attempts it took to succeed.
+    ///
+    /// This is equivalent to `.await`ing the `Retry` directly, but is useful for
+    /// recording "succeeded after N attempts" metrics. The first attempt counts,
+    /// so an operation that succeeds immediately reports `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # impl std::error::Error for MyError {}
+    /// # async fn fetch() -> Result<String, MyError> { Ok(String::new()) }
+    /// # async fn example() -> Result<(), MyError> {
+    /// let (data, attempts) = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(3),
+    ///     move || async move { fetch().await }
+    /// )
+    /// .run_with_attempts()
+    /// .await?;
+    ///
+    /// println!("Fetched {} bytes after {} attempts", data.len(), attempts);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_with_attempts(self) -> Result<(T, usize), E> {
+        let (result, stats) = self.execute().await;
+        result.map(|value| (value, stats.attempts))
+    }
+
+    /// Runs the retry loop, returning the final result together with
+    /// [`RetryStats`] describing how it went.
+    ///
+    /// Unlike [`run_with_attempts()`](Retry::run_with_attempts), the stats are
+    /// returned even when the operation ultimately fails, which makes this the
+    /// method to use for post-mortem analysis. See
+    /// [`with_record_delays()`](Retry::with_record_delays) for an example.
+    pub async fn run_with_stats(self) -> (Result<T, E>, RetryStats) {
+        self.execute().await
+    }
+
+    /// Runs the retry loop, converting the final error into `E2` with
+    /// [`From`].
+    ///
+    /// This saves a `map_err` when the operation's error type differs from
+    /// the one the caller returns, such as a `Box<dyn Error>`. Only the error
+    /// that ends the loop is converted: the condition and every other hook
+    /// still see the operation's own error type `E`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::error::Error;
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # impl std::error::Error for MyError {}
+    /// # async fn fetch() -> Result<String, MyError> { Ok(String::new()) }
+    /// async fn load() -> Result<String, Box<dyn Error + Send + Sync>> {
+    ///     let data = Retry::new(
+    ///         FixedDelay::new(Duration::from_millis(100)).take(3),
+    ///         move || async move { fetch().await }
+    ///     )
+    ///     .run_into::<Box<dyn Error + Send + Sync>>()
+    ///     .await?;
+    ///     Ok(data)
+    /// }
+    /// ```
+    pub async fn run_into<E2>(self) -> Result<T, E2>
+    where
+        E2: From<E>,
+    {
+        self.execute().await.0.map_err(E2::from)
+    }
+
+    /// Runs the retry loop, wrapping the final error in a [`RetryError`].
+    ///
+    /// The wrapper records the number of attempts and the time taken, and
+    /// its `Display` includes both ahead of the original error, e.g. "failed
+    /// after 5 attempts in 3.2s: connection refused". Awaiting the `Retry`
+    /// directly still returns the bare `E`. See [`RetryError`] for an example.
+    pub async fn run_wrapped(self) -> Result<T, RetryError<E>> {
+        let (result, stats, elapsed) = self.execute_timed().await;
+        result.map_err(|e| RetryError::new(e, stats.attempts, elapsed))
+    }
+
+    /// Converts this `Retry` into a [`LocalRetry`] that can be awaited without
+    /// requiring the operation or its future to be `Send`.
+    ///
+    /// Use this on single-threaded runtimes (e.g., a current-thread Tokio
+    /// runtime or WASM) when the operation holds `!Send` state such as an `Rc`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::rc::Rc;
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn fetch(_: &str) -> Result<String, MyError> { Ok(String::new()) }
+    /// # async fn example() {
+    /// let url = Rc::new(String::from("https://example.com"));
+    ///
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(3),
+    ///     move || {
+    ///         let url = url.clone();
+    ///         async move { fetch(&url).await }
+    ///     }
+    /// )
+    /// .local()
+    /// .await;
+    /// # }
+    /// ```
+    pub fn local(self) -> LocalRetry<S, O, C, K, B> {
+        LocalRetry { inner: self }
+    }
 
-                match result {
-                    // Success, return the value.
-                    Ok(value) => {
-                        #[cfg(feature = "logging")]
-                        log::trace!("Operation succeeded on attempt {}", _attempt);
-                        return Ok(value);
-                    }
-                    // Failure, check if we should retry.
-                    Err(e) => {
+    /// Cancels the whole retry loop if it has not finished after `timeout`.
+    ///
+    /// Unlike [`with_max_duration()`](Retry::with_max_duration), which only
+    /// stops between attempts and returns the last operation error, this
+    /// races the entire loop against a timer. When the timer fires, the
+    /// in-flight attempt or backoff sleep is dropped immediately and the
+    /// result is `Err(RetryTimeout)`. Otherwise it is `Ok` with the retry's
+    /// own result.
+    ///
+    /// The timer always uses the runtime's clock, not a [`Clock`] configured
+    /// with [`with_clock()`](Retry::with_clock).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// match Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_hard_timeout(Duration::from_secs(5))
+    /// .await
+    /// {
+    ///     Ok(Ok(())) => println!("succeeded"),
+    ///     Ok(Err(e)) => eprintln!("gave up: {}", e),
+    ///     Err(timeout) => eprintln!("{}", timeout),
+    /// }
+    /// # }
+    /// ```
+    pub fn with_hard_timeout(self, timeout: Duration) -> HardTimeout<S, O, C, K, B> {
+        HardTimeout {
+            inner: self,
+            timeout,
+        }
+    }
+
+    /// Calls `hook` once when the retry loop finishes, whatever the outcome.
+    ///
+    /// The hook receives the final result, the number of attempts made, and
+    /// the total time taken as measured by the configured [`Clock`]. It runs
+    /// exactly once, after the last attempt, which makes it a good place to
+    /// emit one structured log line or metric per operation. Per-attempt hooks
+    /// such as [`with_before_attempt()`](Retry::with_before_attempt) still run
+    /// as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn operation() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let result = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(100)).take(3),
+    ///     move || async move { operation().await }
+    /// )
+    /// .with_on_complete(|result, attempts, elapsed| {
+    ///     println!("ok={} attempts={} elapsed={:?}", result.is_ok(), attempts, elapsed);
+    /// })
+    /// .await;
+    /// # }
+    /// ```
+    pub fn with_on_complete<H>(self, hook: H) -> OnComplete<S, O, C, K, B, H>
+    where
+        H: FnOnce(&Result<T, E>, usize, Duration),
+    {
+        OnComplete { inner: self, hook }
+    }
+
+    /// Shares this retry's run with concurrent calls using the same `key`.
+    ///
+    /// If another call with the same key is already in flight on
+    /// `coordinator`, this one doesn't run its operation at all; it waits for
+    /// that run and returns a clone of its result. Otherwise it runs as usual
+    /// and hands its result to every call that joined in the meantime. This
+    /// keeps many tasks from stampeding a failing resource in lockstep.
+    ///
+    /// See [`SingleFlight`] for an example.
+    pub fn with_single_flight(
+        self,
+        key: impl Into<String>,
+        coordinator: Arc<SingleFlight<T, E>>,
+    ) -> SingleFlightRetry<S, O, C, K, B, T, E> {
+        SingleFlightRetry {
+            inner: self,
+            key: key.into(),
+            coordinator,
+        }
+    }
+
+    /// Runs the retry loop to completion, consuming the `Retry`.
+    ///
+    /// Returns the final result along with the stats of the run.
+    async fn execute(self) -> (Result<T, E>, RetryStats) {
+        let Retry {
+            mut strategy,
+            mut operation,
+            mut config,
+        } = self;
+        config
+            .execute(&mut strategy, &mut operation, &mut |_: &T| true)
+            .await
+    }
+
+    /// Like [`execute()`](Retry::execute), also returning the total time
+    /// taken as measured by the configured clock.
+    async fn execute_timed(self) -> (Result<T, E>, RetryStats, Duration) {
+        let Retry {
+            mut strategy,
+            mut operation,
+            mut config,
+        } = self;
+        let start = config.clock.now();
+        let (result, stats) = config
+            .execute(&mut strategy, &mut operation, &mut |_: &T| true)
+            .await;
+        let elapsed = config.clock.now().saturating_duration_since(start);
+        (result, stats, elapsed)
+    }
+}
+
+/// How often a backoff sleep checks the abort flag set by
+/// [`Retry::with_abort_flag()`].
+const ABORT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Sleeps for `delay` on `clock`, returning `false` early if `abort_flag` is set.
+///
+/// Without an abort flag this is a plain sleep. With one, the delay is slept in
+/// slices of at most [`ABORT_POLL_INTERVAL`] so the flag can be polled in
+/// between. This borrows only the clock and flag, not the whole `RetryConfig`,
+/// so the retry future doesn't require the configuration to be `Sync`.
+async fn sleep_unless_aborted<K: Clock>(
+    clock: &K,
+    abort_flag: Option<&AtomicBool>,
+    delay: Duration,
+) -> bool {
+    let Some(flag) = abort_flag.filter(|_| !delay.is_zero()) else {
+        clock.sleep(delay).await;
+        return !abort_flag.is_some_and(|flag| flag.load(Ordering::Acquire));
+    };
+    let mut remaining = delay;
+    loop {
+        if flag.load(Ordering::Acquire) {
+            return false;
+        }
+        if remaining.is_zero() {
+            return true;
+        }
+        let slice = remaining.min(ABORT_POLL_INTERVAL);
+        clock.sleep(slice).await;
+        remaining -= slice;
+    }
+}
+
+/// Races `sleep` against the wakeup signal, if one is still pending.
+///
+/// Returns `None` if the signal resolved first, consuming it, or the output
+/// of `sleep` otherwise.
+async fn sleep_unless_woken(
+    sleep: impl Future<Output = bool>,
+    signal: &mut Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+) -> Option<bool> {
+    let Some(wakeup) = signal.as_mut() else {
+        return Some(sleep.await);
+    };
+    let mut sleep = std::pin::pin!(sleep);
+    let slept = std::future::poll_fn(|cx| {
+        if wakeup.as_mut().poll(cx).is_ready() {
+            return std::task::Poll::Ready(None);
+        }
+        sleep.as_mut().poll(cx).map(Some)
+    })
+    .await;
+    if slept.is_none() {
+        *signal = None;
+    }
+    slept
+}
+
+// Implementation block for the retry loop itself, which borrows the
+// configuration and drives a strategy and operation it doesn't own.
+impl<C, K, B> RetryConfig<C, K, B>
+where
+    K: Clock,
+{
+    /// Returns `true` if the abort flag is configured and set.
+    fn is_aborted(&self) -> bool {
+        self.options
+            .abort_flag
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Acquire))
+    }
+
+    /// Contains the core retry loop logic.
+    ///
+    /// `Ok` values rejected by `validate` are retried like retryable errors.
+    /// Returns the final result along with the stats of the run.
+    async fn execute<S, O, V, F, T, E>(
+        &mut self,
+        strategy: &mut S,
+        operation: &mut O,
+        validate: &mut V,
+    ) -> (Result<T, E>, RetryStats)
+    where
+        S: Backoff,
+        O: FnMut() -> F,
+        V: Validator<T, E>,
+        F: Future<Output = Result<T, E>>,
+        C: RetryCondition<E>,
+        B: CircuitGuard<E>,
+        E: Display,
+    {
+        let start_time = self.clock.now();
+
+        let mut stats = RetryStats {
+            attempts: 0,
+            delays: self.options.record_delays.then(Vec::new),
+            degraded: false,
+            total_exec: Duration::ZERO,
+            total_sleep: Duration::ZERO,
+        };
+        let mut zero_delays = 0;
+        let mut last_error = None;
+
+        // Run every attempt inside the span set with `with_span()`, if any.
+        #[cfg(feature = "tracing")]
+        let span = self.options.span.clone();
+        #[cfg(feature = "tracing")]
+        let operation = &mut || {
+            use tracing::Instrument;
+            let span = span.clone().unwrap_or_else(tracing::Span::none);
+            let future = span.in_scope(&mut *operation);
+            future.instrument(span)
+        };
+
+        // Desynchronize the first attempt from other processes starting now.
+        #[cfg(feature = "jitter")]
+        if let Some(splay) = self.options.startup_splay {
+            let delay = match self.options.splay_source.as_mut() {
+                Some(source) => source.next_duration(Duration::ZERO, splay),
+                None => ThreadRngSource.next_duration(Duration::ZERO, splay),
+            };
+            self.clock.sleep(delay).await;
+            stats.total_sleep = self.clock.now().saturating_duration_since(start_time);
+        }
+
+        loop {
+            // Fail fast if the circuit breaker rejects the attempt.
+            if let Err(e) = self.circuit_breaker.before_attempt() {
+                #[cfg(feature = "logging")]
+                log::error!(
+                    operation_name = self.options.operation_name.as_deref(),
+                    attempt = stats.attempts;
+                    "Retry failed: circuit breaker is open."
+                );
+                return (Err(e), stats);
+            }
+
+            stats.attempts += 1;
+            let attempt = stats.attempts;
+            #[cfg(feature = "global-metrics")]
+            metrics::record_attempt(attempt);
+
+            if let Some(counter) = &self.options.attempt_counter {
+                counter.store(attempt, Ordering::Relaxed);
+            }
+            if let Some(handle) = &self.options.handle {
+                handle.record_attempt(attempt);
+            }
+
+            if let Some(before_attempt) = self.options.before_attempt.as_mut() {
+                before_attempt(attempt);
+            }
+
+            // Execute the async operation.
+            let exec_start = self.clock.now();
+            let clock = &self.clock;
+            let hedge_delay = self.options.hedge_delay;
+            let run_attempt = async {
+                match hedge_delay {
+                    Some(hedge_delay) => hedge::run_hedged(operation, clock, hedge_delay).await,
+                    None => operation().await,
+                }
+            };
+            // Cut the attempt off once a strict maximum duration runs out. A
+            // deadline too far away to represent is never reached.
+            let result = match self.condition.attempt_deadline() {
+                Some((max_duration, timed_out)) => match start_time.checked_add(max_duration) {
+                    Some(deadline) => timeout::within(clock, deadline, run_attempt)
+                        .await
+                        .unwrap_or_else(|| Err(timed_out(RetryTimeout::new(max_duration)))),
+                    None => run_attempt.await,
+                },
+                None => run_attempt.await,
+            };
+            let latency = self.clock.now().saturating_duration_since(exec_start);
+            stats.total_exec += latency;
+
+            // Success, return the value unless the validator rejects it. A
+            // rejected value counts as a failure for the circuit breaker.
+            let accepted = result.as_ref().is_ok_and(|value| validate.accept(value));
+            self.circuit_breaker.record(accepted);
+            if accepted {
+                #[cfg(feature = "logging")]
+                log::trace!(
+                    operation_name = self.options.operation_name.as_deref(),
+                    attempt;
+                    "Operation succeeded on attempt {}",
+                    attempt
+                );
+                if let Some(threshold) = self.options.degraded_threshold {
+                    if attempt > threshold {
                         #[cfg(feature = "logging")]
                         log::warn!(
-                            "Operation failed on attempt {} with error: {}",
-                            _attempt,
-                            e
+                            operation_name = self.options.operation_name.as_deref(),
+                            attempt,
+                            degraded_threshold = threshold;
+                            "Degraded success: operation needed {} attempts (threshold {})",
+                            attempt,
+                            threshold
                         );
+                        stats.degraded = true;
+                    }
+                }
+                return (result, stats);
+            }
+            #[cfg(feature = "logging")]
+            if result.is_ok() {
+                log::log!(
+                    self.options.log_level.unwrap_or(log::Level::Warn),
+                    operation_name = self.options.operation_name.as_deref(),
+                    attempt;
+                    "Operation returned an invalid value on attempt {}",
+                    attempt
+                );
+            }
 
-                        // Check max total duration limit
-                        if let Some(max_duration) = self.max_duration {
-                            if start_time.elapsed() >= max_duration {
-                                #[cfg(feature = "logging")]
-                                log::error!(
-                                    "Retry failed: max duration ({:?}) exceeded.",
-                                    max_duration
-                                );
-                                return Err(e); // Exhausted time
-                            }
-                        }
+            // A rejected value is reported as the last error, if there was one.
+            let invalid = result.is_ok();
+            let synthesized = invalid && last_error.is_none();
+            let result = match result {
+                Ok(value) => match last_error.take() {
+                    Some(e) => Err(e),
+                    None => validate.reject(value, attempt),
+                },
+                Err(e) => Err(e),
+            };
 
-                        // Always retry with AlwaysRetry condition
-
-                        // Get next backoff duration
-                        if let Some(delay) = self.strategy.next() {
-                            // Check if the *sleep itself* would exceed max duration
-                            if let Some(max_duration) = self.max_duration {
-                                if start_time.elapsed() + delay > max_duration {
-                                    #[cfg(feature = "logging")]
-                                    log::error!(
-                                        "Retry failed: next delay ({:?}) would exceed max duration.",
-                                        delay
-                                    );
-                                    return Err(e); // Sleep would exceed total duration
-                                }
-                            }
+            // Failure, check if we should retry.
+            if let (Some(handle), Err(e), false) = (&self.options.handle, &result, invalid) {
+                handle.record_error(e.to_string());
+            }
+            #[cfg(feature = "logging")]
+            if let (Err(e), false) = (&result, invalid) {
+                log::log!(
+                    self.options.log_level.unwrap_or(log::Level::Warn),
+                    operation_name = self.options.operation_name.as_deref(),
+                    attempt,
+                    error_display:% = e;
+                    "Operation failed on attempt {} with error: {}",
+                    attempt,
+                    e
+                );
+            }
 
-                            // Perform the runtime-agnostic sleep
-                            #[cfg(feature = "logging")]
-                            log::trace!("Retrying after delay of {:?}", delay);
-                            sleep::sleep(delay).await;
-                        } else {
-                            // Backoff strategy is exhausted
-                            #[cfg(feature = "logging")]
-                            log::error!(
-                                "Retry failed: backoff strategy exhausted after {} attempts.",
-                                _attempt
-                            );
-                            return Err(e);
-                        }
+            if self.options.retries_disabled {
+                #[cfg(feature = "logging")]
+                log::error!(
+                    operation_name = self.options.operation_name.as_deref(),
+                    attempt;
+                    "Retry failed: retries are disabled."
+                );
+                return (result, stats);
+            }
+
+            // Check the retry condition; invalid values are always retried.
+            let action = match &result {
+                Err(e) if !invalid => self.condition.action(e),
+                _ => RetryAction::Retry,
+            };
+            if action == RetryAction::Stop {
+                #[cfg(feature = "logging")]
+                if let Err(e) = &result {
+                    log::error!(
+                        operation_name = self.options.operation_name.as_deref(),
+                        attempt,
+                        error_display:% = e;
+                        "Retry failed: error is not retryable."
+                    );
+                }
+                return (result, stats); // Not a retryable error
+            }
+
+            let elapsed = self.clock.now().saturating_duration_since(start_time);
+
+            // Check max total duration limit
+            if let Some(max_duration) = self.options.max_duration {
+                if elapsed >= max_duration {
+                    #[cfg(feature = "logging")]
+                    log::error!(
+                        operation_name = self.options.operation_name.as_deref(),
+                        attempt,
+                        max_duration_ms = max_duration.as_millis() as u64;
+                        "Retry failed: max duration ({:?}) exceeded.",
+                        max_duration
+                    );
+                    let result = result.map_err(|e| self.condition.on_timeout(e));
+                    return (result, stats); // Exhausted time
+                }
+            }
+
+            if self.is_aborted() {
+                #[cfg(feature = "logging")]
+                log::error!(
+                    operation_name = self.options.operation_name.as_deref(),
+                    attempt;
+                    "Retry failed: aborted."
+                );
+                return (result, stats);
+            }
+
+            if let Some(max_attempts) = self.options.max_attempts {
+                if attempt >= max_attempts {
+                    #[cfg(feature = "logging")]
+                    log::error!(
+                        operation_name = self.options.operation_name.as_deref(),
+                        attempt;
+                        "Retry failed: maximum of {} attempts reached.",
+                        max_attempts
+                    );
+                    return (result, stats);
+                }
+            }
+
+            // Get next backoff duration
+            // This also implicitly handles (Max Retries) if the
+            // strategy itself is limited (e.g., via `.take(n)` or
+            // `with_max_retries()`).
+            if let Some(delay) = strategy.next() {
+                // The condition may override the strategy's delay.
+                let delay = match (action, self.options.delay_transform.as_mut()) {
+                    (RetryAction::RetryAfter(delay), _) => delay,
+                    (_, Some(transform)) => transform(delay, attempt),
+                    (_, None) => delay,
+                };
+                let delay = match action {
+                    RetryAction::Retry if self.options.latency_aware_delay => delay.max(latency),
+                    _ => delay,
+                };
+
+                // Stop a strategy that keeps retrying without ever waiting.
+                zero_delays = if delay.is_zero() { zero_delays + 1 } else { 0 };
+                if let Some(max_zero_delays) = self.options.spin_guard {
+                    if zero_delays > max_zero_delays {
+                        #[cfg(feature = "logging")]
+                        log::error!(
+                            operation_name = self.options.operation_name.as_deref(),
+                            attempt;
+                            "Retry failed: {} consecutive zero delays exceeded the spin guard.",
+                            zero_delays
+                        );
+                        return (result, stats);
+                    }
+                }
+
+                // Check if the *sleep itself* would exceed max duration
+                let mut delay = delay;
+                if let Some(max_duration) = self.options.max_duration {
+                    // The first retry may be squeezed into the time left.
+                    // `elapsed < max_duration` was checked above.
+                    if elapsed + delay > max_duration && self.options.min_one_retry && attempt == 1
+                    {
+                        delay = max_duration - elapsed;
+                    }
+                    if elapsed + delay > max_duration {
+                        #[cfg(feature = "logging")]
+                        log::error!(
+                            operation_name = self.options.operation_name.as_deref(),
+                            attempt,
+                            delay_ms = delay.as_millis() as u64;
+                            "Retry failed: next delay ({:?}) would exceed max duration.",
+                            delay
+                        );
+                        let result = result.map_err(|e| self.condition.on_timeout(e));
+                        return (result, stats); // Sleep would exceed total duration
                     }
                 }
+
+                // Perform the runtime-agnostic sleep
+                #[cfg(feature = "logging")]
+                log::trace!(
+                    operation_name = self.options.operation_name.as_deref(),
+                    attempt,
+                    delay_ms = delay.as_millis() as u64;
+                    "Retrying after delay of {:?}",
+                    delay
+                );
+                let abort_flag = self.options.abort_flag.as_deref();
+                let sleep_start = self.clock.now();
+                #[cfg(feature = "tokio-timer")]
+                if let Some(countdown) = &self.options.countdown {
+                    countdown.send_replace(Some(Countdown {
+                        attempt,
+                        delay,
+                        started_at: sleep_start,
+                    }));
+                }
+                let sleep = sleep_unless_aborted(&self.clock, abort_flag, delay);
+                let slept = sleep_unless_woken(sleep, &mut self.options.wakeup_signal).await;
+                let slept_for = self.clock.now().saturating_duration_since(sleep_start);
+                stats.total_sleep += slept_for;
+                if slept.is_none() {
+                    #[cfg(feature = "logging")]
+                    log::debug!(
+                        operation_name = self.options.operation_name.as_deref(),
+                        attempt;
+                        "Wakeup signal received; retrying early."
+                    );
+                }
+                if slept == Some(false) {
+                    #[cfg(feature = "logging")]
+                    log::error!(
+                        operation_name = self.options.operation_name.as_deref(),
+                        attempt;
+                        "Retry failed: aborted during backoff."
+                    );
+                    return (result, stats);
+                }
+                if let Some(delays) = stats.delays.as_mut() {
+                    // A sleep cut short by the wakeup signal only counts the
+                    // time actually spent.
+                    delays.push(if slept.is_some() { delay } else { slept_for });
+                }
+                if let (Err(e), false) = (result, synthesized) {
+                    last_error = Some(e);
+                }
+            } else {
+                // Backoff strategy is exhausted
+                #[cfg(feature = "logging")]
+                log::error!(
+                    operation_name = self.options.operation_name.as_deref(),
+                    attempt;
+                    "Retry failed: backoff strategy exhausted after {} attempts.",
+                    attempt
+                );
+                return (result, stats);
             }
-        })
+        }
     }
 }
 
-/// The core retry logic, implemented via `IntoFuture` for custom conditions.
-impl<S, O, C, F, T, E> IntoFuture for Retry<S, O, C>
+// Implementation block for running a Retry in the background.
+#[cfg(feature = "tokio-timer")]
+impl<S, O, C, K, B, F, T, E> Retry<S, O, C, K, B>
 where
     S: Backoff + Send + 'static,
+    K: Clock + Send + Sync + 'static,
+    B: CircuitGuard<E> + Send + 'static,
     O: FnMut() -> F + Send + 'static,
-    C: FnMut(&E) -> bool + Send + 'static,
+    C: RetryCondition<E> + Send + 'static,
     F: Future<Output = Result<T, E>> + Send,
-    E: Error + Send,
-    T: Send,
+    E: Display + Send + 'static,
+    T: Send + 'static,
 {
-    type Output = Result<T, E>;
-
-    // We box the future to avoid complex type signatures in the return.
-    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
-
-    /// Contains the core retry loop logic.
-    fn into_future(mut self) -> <Retry<S, O, C> as IntoFuture>::IntoFuture {
-        Box::pin(async move {
-            let start_time = Instant::now();
-            #[allow(unused_variables)]
-            let mut attempt = 0;
-
-            loop {
-                _attempt += 1;
-
-                // Execute the async operation.
-                let result = (self.operation)().await;
+    /// Spawns the retry onto the current Tokio runtime and returns a handle
+    /// to its eventual result.
+    ///
+    /// This is shorthand for `tokio::spawn(retry.into_future())`, useful for
+    /// fire-and-forget work such as best-effort telemetry uploads. Dropping
+    /// the handle detaches the task; it keeps retrying in the background. It
+    /// requires the `tokio-timer` feature flag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::ExponentialBackoff};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn upload_metrics() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let handle = Retry::new(
+    ///     ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(5),
+    ///     move || async move { upload_metrics().await }
+    /// )
+    /// .spawn();
+    ///
+    /// // ... do other work, then optionally check the outcome
+    /// let result = handle.await;
+    /// # }
+    /// ```
+    pub fn spawn(self) -> tokio::task::JoinHandle<Result<T, E>> {
+        tokio::spawn(self.into_future())
+    }
 
-                match result {
-                    // Success, return the value.
-                    Ok(value) => {
-                        #[cfg(feature = "logging")]
-                        log::trace!("Operation succeeded on attempt {}", _attempt);
-                        return Ok(value);
-                    }
-                    // Failure, check if we should retry.
-                    Err(e) => {
-                        #[cfg(feature = "logging")]
-                        log::warn!("Operation failed on attempt {} with error: {}", _attempt, e);
-
-                        // Check max total duration limit
-                        if let Some(max_duration) = self.max_duration {
-                            if start_time.elapsed() >= max_duration {
-                                #[cfg(feature = "logging")]
-                                log::error!(
-                                    "Retry failed: max duration ({:?}) exceeded.",
-                                    max_duration
-                                );
-                                return Err(e); // Exhausted time
-                            }
-                        }
+    /// Spawns the retry like [`spawn()`](Retry::spawn), also returning a
+    /// [`RetryHandle`] to its live progress.
+    ///
+    /// This replaces any handle set with [`with_handle()`](Retry::with_handle).
+    /// It requires the `tokio-timer` feature flag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # async fn reconnect() -> Result<(), MyError> { Ok(()) }
+    /// # async fn example() {
+    /// let (task, handle) = Retry::new(
+    ///     FixedDelay::new(Duration::from_secs(1)).take(60),
+    ///     move || async move { reconnect().await }
+    /// )
+    /// .spawn_with_handle();
+    ///
+    /// println!(
+    ///     "reconnecting: attempt {}, last error {:?}",
+    ///     handle.attempts_made(),
+    ///     handle.last_error()
+    /// );
+    /// let result = task.await;
+    /// # }
+    /// ```
+    pub fn spawn_with_handle(self) -> (tokio::task::JoinHandle<Result<T, E>>, RetryHandle) {
+        let handle = RetryHandle::new();
+        (self.with_handle(handle.clone()).spawn(), handle)
+    }
 
-                        // Check the retry condition
-                        if !(self.condition)(&e) {
-                            #[cfg(feature = "logging")]
-                            log::error!("Retry failed: error is not retryable.");
-                            return Err(e); // Not a retryable error
+    /// Returns the first successful value at once, but keeps retrying in the
+    /// background while it is only a partial result.
+    ///
+    /// This is for APIs that can answer with a usable but degraded value, such
+    /// as a search with some shards missing. The retry loop runs on a spawned
+    /// task, treating values that are not complete according to `is_complete`
+    /// like retryable errors (as with [`with_validator()`](Retry::with_validator)).
+    /// The first value it gets, complete or not, is returned at once, as is
+    /// the final error if it never gets one. After an incomplete value the
+    /// same loop carries on in the background, with the same attempt count,
+    /// maximum duration and delays, and if it gets a complete value before
+    /// it gives up, `on_upgrade` is called with it; otherwise the upgrade is
+    /// silently dropped.
+    ///
+    /// Dropping the returned future before it completes cancels the loop.
+    /// It requires the `tokio-timer` feature flag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime, or if the runtime shuts
+    /// down and cancels the background task before the first value is
+    /// returned. Resumes the panic if the operation panics before then.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_retry::{Retry, backoff::FixedDelay};
+    /// use std::time::Duration;
+    ///
+    /// # #[derive(Debug, Clone)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+    /// # }
+    /// # struct Results { partial: bool }
+    /// # async fn search() -> Result<Results, MyError> { Ok(Results { partial: false }) }
+    /// # fn render(_: &Results) {}
+    /// # async fn example() -> Result<(), MyError> {
+    /// let results = Retry::new(
+    ///     FixedDelay::new(Duration::from_millis(500)).take(5),
+    ///     move || async move { search().await }
+    /// )
+    /// .with_background_upgrade(
+    ///     |results| !results.partial,
+    ///     |full| render(&full),
+    /// )
+    /// .await?;
+    ///
+    /// // Show what we have now; `render` runs again if the full results arrive
+    /// render(&results);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_background_upgrade<P, U>(
+        self,
+        mut is_complete: P,
+        on_upgrade: U,
+    ) -> Result<T, E>
+    where
+        P: FnMut(&T) -> bool + Send + 'static,
+        U: FnOnce(T) + Send + 'static,
+    {
+        let Retry {
+            mut strategy,
+            mut operation,
+            mut config,
+        } = self;
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let task = tokio::spawn(async move {
+            // The operation parks each value here for the validator, which
+            // hands the first incomplete one to the caller.
+            let slot = std::sync::Mutex::new(None);
+            let mut tx = Some(tx);
+            let (result, _) = config
+                .execute(
+                    &mut strategy,
+                    &mut || {
+                        let attempt = operation();
+                        let slot = &slot;
+                        async move {
+                            *slot.lock().unwrap() = Some(attempt.await?);
+                            Ok(())
                         }
-
-                        // Get next backoff duration
-                        // This also implicitly handles (Max Retries) if the
-                        // strategy itself is limited (e.g., via `.take(n)` or
-                        // `with_max_retries()`).
-                        if let Some(delay) = self.strategy.next() {
-                            // Check if the *sleep itself* would exceed max duration
-                            if let Some(max_duration) = self.max_duration {
-                                if start_time.elapsed() + delay > max_duration {
-                                    #[cfg(feature = "logging")]
-                                    log::error!(
-                                        "Retry failed: next delay ({:?}) would exceed max duration.",
-                                        delay
-                                    );
-                                    return Err(e); // Sleep would exceed total duration
-                                }
+                    },
+                    &mut |_: &()| {
+                        let mut slot = slot.lock().unwrap();
+                        let value = slot.take().expect("value stored by the attempt");
+                        let complete = is_complete(&value);
+                        match tx.take() {
+                            Some(tx) if !complete => {
+                                let _ = tx.send(Ok(value));
+                            }
+                            taken => {
+                                tx = taken;
+                                *slot = Some(value);
                             }
-
-                            // Perform the runtime-agnostic sleep
-                            #[cfg(feature = "logging")]
-                            log::trace!("Retrying after delay of {:?}", delay);
-                            sleep::sleep(delay).await;
-                        } else {
-                            // Backoff strategy is exhausted
-                            #[cfg(feature = "logging")]
-                            log::error!(
-                                "Retry failed: backoff strategy exhausted after {} attempts.",
-                                _attempt
-                            );
-                            return Err(e);
                         }
+                        complete
+                    },
+                )
+                .await;
+            let value = slot.into_inner().unwrap();
+            match tx {
+                Some(tx) => {
+                    let _ = tx.send(result.map(|()| value.expect("accepted value")));
+                }
+                None => {
+                    if let Some(value) = value.filter(|value| is_complete(value)) {
+                        on_upgrade(value);
                     }
                 }
             }
-        })
+        });
+
+        let mut task = AbortOnDrop(Some(task));
+        match rx.await {
+            Ok(result) => {
+                // The caller has its value; let the upgrade carry on.
+                task.0 = None;
+                result
+            }
+            Err(_) => match task.0.take().expect("task handle").await {
+                Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+                _ => panic!("the retry task was cancelled before returning a result"),
+            },
+        }
+    }
+}
+
+/// Aborts a spawned task when dropped, unless the handle has been taken.
+#[cfg(feature = "tokio-timer")]
+struct AbortOnDrop<T>(Option<tokio::task::JoinHandle<T>>);
+
+#[cfg(feature = "tokio-timer")]
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        if let Some(task) = &self.0 {
+            task.abort();
+        }
+    }
+}
+
+/// The core retry logic, implemented via `IntoFuture` for any [`RetryCondition`].
+impl<S, O, C, K, B, F, T, E> IntoFuture for Retry<S, O, C, K, B>
+where
+    S: Backoff + Send + 'static,
+    K: Clock + Send + Sync + 'static,
+    B: CircuitGuard<E> + Send + 'static,
+    O: FnMut() -> F + Send + 'static,
+    C: RetryCondition<E> + Send + 'static,
+    F: Future<Output = Result<T, E>> + Send,
+    E: Display + Send,
+    T: Send,
+{
+    type Output = Result<T, E>;
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> <Retry<S, O, C, K, B> as IntoFuture>::IntoFuture {
+        Box::pin(async move { self.execute().await.0 })
     }
 }
@@ -0,0 +1,47 @@
+// Author: Jacques Murray
+
+//! Retrying `!Send` operations on single-threaded runtimes.
+
+use crate::backoff::Backoff;
+use crate::circuit::CircuitGuard;
+use crate::clock::Clock;
+use crate::condition::RetryCondition;
+use crate::Retry;
+use std::fmt::Display;
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+
+/// A [`Retry`] that can be awaited without requiring `Send`.
+///
+/// Created by [`Retry::local()`]. The retry behavior is identical, but the
+/// boxed future it produces is not `Send`, so the operation may capture `!Send`
+/// state such as `Rc` or `RefCell`. It must be awaited on the thread that
+/// created it (e.g., a current-thread Tokio runtime or WASM).
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct LocalRetry<S, O, C, K, B>
+where
+    S: Backoff,
+{
+    pub(crate) inner: Retry<S, O, C, K, B>,
+}
+
+/// The core retry logic, implemented via `IntoFuture` without `Send` bounds.
+impl<S, O, C, K, B, F, T, E> IntoFuture for LocalRetry<S, O, C, K, B>
+where
+    S: Backoff + 'static,
+    K: Clock + 'static,
+    B: CircuitGuard<E> + 'static,
+    O: FnMut() -> F + 'static,
+    C: RetryCondition<E> + 'static,
+    F: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    type Output = Result<T, E>;
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'static>>;
+
+    fn into_future(self) -> <LocalRetry<S, O, C, K, B> as IntoFuture>::IntoFuture {
+        Box::pin(async move { self.inner.execute().await.0 })
+    }
+}
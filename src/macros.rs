@@ -0,0 +1,63 @@
+// Author: Jacques Murray
+
+//! The [`retry!`](crate::retry) macro for terse call sites.
+
+/// Retries an async block with the given strategy and awaits the result.
+///
+/// `retry!(strategy, { ... })` is shorthand for
+/// `Retry::new(strategy, move || async move { ... }).await`, so it must be
+/// used inside an `async` context. The block runs once per attempt and must
+/// evaluate to a `Result`.
+///
+/// Optional trailing arguments configure the [`Retry`](crate::Retry), in this
+/// order:
+///
+/// - `condition = ...`: Passed to [`Retry::with_condition()`](crate::Retry::with_condition)
+/// - `max_duration = ...`: Passed to [`Retry::with_max_duration()`](crate::Retry::with_max_duration)
+///
+/// The block becomes a fresh `async move` future on every attempt, so any
+/// variables it captures must be `Copy` (e.g., integers or `&'static`
+/// references). To share non-`Copy` state such as an `Arc`, use
+/// [`Retry::with_state()`](crate::Retry::with_state) instead.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_retry::{retry, backoff::ExponentialBackoff};
+/// use std::time::Duration;
+///
+/// # #[derive(Debug, Clone)]
+/// # enum ApiError { Network, Auth }
+/// # impl std::fmt::Display for ApiError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+/// # }
+/// # async fn call_api() -> Result<String, ApiError> { Ok(String::new()) }
+/// # async fn example() {
+/// let strategy = ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(3);
+///
+/// let result = retry!(strategy, { call_api().await });
+///
+/// let strategy = ExponentialBackoff::new(Duration::from_millis(100)).with_max_retries(3);
+/// let result = retry!(
+///     strategy,
+///     { call_api().await },
+///     condition = |e: &ApiError| matches!(e, ApiError::Network),
+///     max_duration = Duration::from_secs(10),
+/// );
+/// # }
+/// ```
+#[macro_export]
+macro_rules! retry {
+    (
+        $strategy:expr,
+        $body:block
+        $(, condition = $condition:expr)?
+        $(, max_duration = $max_duration:expr)?
+        $(,)?
+    ) => {{
+        let retry = $crate::Retry::new($strategy, move || async move $body);
+        $(let retry = retry.with_condition($condition);)?
+        $(let retry = retry.with_max_duration($max_duration);)?
+        retry.await
+    }};
+}
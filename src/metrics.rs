@@ -0,0 +1,48 @@
+// Author: Jacques Murray
+
+//! Process-wide attempt counters, without a metrics crate.
+//!
+//! With the `global-metrics` feature, every retry loop in the process adds
+//! to two global counters, readable at any time with [`total_attempts()`]
+//! and [`total_retries()`]. This gives basic observability (e.g., exporting
+//! the counters from a health endpoint) without pulling in `metrics` or
+//! `prometheus`. The counters only ever grow; compare two readings to get
+//! the activity in between.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_retry::metrics;
+//!
+//! let retries_before = metrics::total_retries();
+//! // ... run some retries ...
+//! let retries = metrics::total_retries() - retries_before;
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The number of attempts made by all retry loops, including first attempts.
+static TOTAL_ATTEMPTS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of attempts that were retries, i.e. not the first of a loop.
+static TOTAL_RETRIES: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the number of attempts started by all retry loops in the process,
+/// including each loop's first attempt.
+pub fn total_attempts() -> u64 {
+    TOTAL_ATTEMPTS.load(Ordering::Relaxed)
+}
+
+/// Returns the number of retries made by all retry loops in the process,
+/// i.e. the attempts after each loop's first.
+pub fn total_retries() -> u64 {
+    TOTAL_RETRIES.load(Ordering::Relaxed)
+}
+
+/// Counts the start of attempt number `attempt` (starting at 1).
+pub(crate) fn record_attempt(attempt: usize) {
+    TOTAL_ATTEMPTS.fetch_add(1, Ordering::Relaxed);
+    if attempt > 1 {
+        TOTAL_RETRIES.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,196 @@
+// Author: Jacques Murray
+
+//! Polling an operation until its result is ready.
+
+use crate::backoff::Backoff;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+
+/// The error returned when [`poll_until()`]'s strategy is exhausted before
+/// the polled value is ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotReady {
+    polls: usize,
+}
+
+impl NotReady {
+    pub(crate) fn new(polls: usize) -> Self {
+        Self { polls }
+    }
+
+    /// Returns the number of times the operation was polled.
+    pub fn polls(&self) -> usize {
+        self.polls
+    }
+}
+
+impl Display for NotReady {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value not ready after {} polls", self.polls)
+    }
+}
+
+impl Error for NotReady {}
+
+/// Polls `operation` until `ready` returns `true` for its value.
+///
+/// This is for "retry while `Ok` but not ready yet" loops, such as waiting
+/// for a background job to finish. Each `Ok(value)` is passed to `ready`; if
+/// it returns `true`, the value is returned. Otherwise the loop sleeps for the
+/// strategy's next delay and polls again. By default an `Err` is returned
+/// immediately without retrying; see
+/// [`with_max_consecutive_failures()`](PollUntil::with_max_consecutive_failures)
+/// to tolerate errors.
+///
+/// If the strategy is exhausted before the value is ready, the last error is
+/// returned if the final poll failed, and otherwise a [`NotReady`] converted
+/// into the operation's error type with `From<NotReady>`. A value that is not
+/// ready is never returned.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_retry::{poll_until, backoff::FixedDelay};
+/// use std::time::Duration;
+///
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # enum JobStatus { Running, Done }
+/// # #[derive(Debug, Clone)]
+/// # struct MyError;
+/// # impl From<async_retry::NotReady> for MyError {
+/// #     fn from(_: async_retry::NotReady) -> Self { MyError }
+/// # }
+/// # async fn job_status() -> Result<JobStatus, MyError> { Ok(JobStatus::Done) }
+/// # async fn example() -> Result<(), MyError> {
+/// let status = poll_until(
+///     FixedDelay::new(Duration::from_secs(1)).take(60),
+///     move || async move { job_status().await },
+///     |status| *status == JobStatus::Done,
+/// )
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn poll_until<S, O, P, F, T, E>(strategy: S, operation: O, ready: P) -> PollUntil<S, O, P>
+where
+    S: Backoff,
+    O: FnMut() -> F,
+    F: Future<Output = Result<T, E>>,
+    P: FnMut(&T) -> bool,
+    E: From<NotReady>,
+{
+    PollUntil {
+        strategy,
+        operation,
+        ready,
+        max_consecutive_failures: 1,
+    }
+}
+
+/// A polling loop created by [`poll_until()`].
+///
+/// Await it directly, or configure it further first.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct PollUntil<S, O, P>
+where
+    S: Backoff,
+{
+    strategy: S,
+    operation: O,
+    ready: P,
+    max_consecutive_failures: usize,
+}
+
+impl<S, O, P> PollUntil<S, O, P>
+where
+    S: Backoff,
+{
+    /// Keeps polling through errors until `max` of them occur in a row.
+    ///
+    /// Every `Ok` value, ready or not, resets the count, so a long-running
+    /// poll that occasionally makes progress is not stopped by errors spread
+    /// out over time. Errors are followed by the strategy's next delay, just
+    /// like not-ready values. A `max` of `0` or `1` returns the first error
+    /// immediately, which is the default.
+    pub fn with_max_consecutive_failures(mut self, max: usize) -> Self {
+        self.max_consecutive_failures = max;
+        self
+    }
+
+    /// Runs the polling loop to completion.
+    async fn execute<F, T, E>(self) -> Result<T, E>
+    where
+        O: FnMut() -> F,
+        F: Future<Output = Result<T, E>>,
+        P: FnMut(&T) -> bool,
+        E: From<NotReady>,
+    {
+        let PollUntil {
+            mut strategy,
+            mut operation,
+            mut ready,
+            max_consecutive_failures,
+        } = self;
+        let mut consecutive_failures = 0;
+        let mut polls = 0;
+
+        loop {
+            let result = operation().await;
+            polls += 1;
+            match &result {
+                Ok(value) => {
+                    consecutive_failures = 0;
+                    if ready(value) {
+                        return result;
+                    }
+                }
+                Err(_) => {
+                    consecutive_failures += 1;
+                    if consecutive_failures >= max_consecutive_failures {
+                        #[cfg(feature = "logging")]
+                        log::error!(
+                            consecutive_failures;
+                            "Polling failed: {} consecutive failures.",
+                            consecutive_failures
+                        );
+                        return result;
+                    }
+                }
+            }
+            match strategy.next() {
+                Some(delay) => {
+                    #[cfg(feature = "logging")]
+                    log::trace!(
+                        delay_ms = delay.as_millis() as u64;
+                        "Polling again after delay of {:?}",
+                        delay
+                    );
+                    crate::sleep::sleep(delay).await;
+                }
+                None => return result.and_then(|_| Err(NotReady::new(polls).into())),
+            }
+        }
+    }
+}
+
+/// The polling logic, implemented via `IntoFuture`.
+impl<S, O, P, F, T, E> IntoFuture for PollUntil<S, O, P>
+where
+    S: Backoff + Send + 'static,
+    O: FnMut() -> F + Send + 'static,
+    P: FnMut(&T) -> bool + Send + 'static,
+    F: Future<Output = Result<T, E>> + Send,
+    T: Send,
+    E: From<NotReady> + Send,
+{
+    type Output = Result<T, E>;
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> <PollUntil<S, O, P> as IntoFuture>::IntoFuture {
+        Box::pin(async move { self.execute().await })
+    }
+}
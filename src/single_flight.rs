@@ -0,0 +1,252 @@
+// Author: Jacques Murray
+
+//! Sharing one retry run between concurrent identical requests.
+//!
+//! When many tasks retry the same failing resource, they back off
+//! independently and then hit it again together. A [`SingleFlight`]
+//! coordinator lets concurrent `Retry` calls with the same key share a single
+//! run: the first caller (the leader) runs its retry loop, and every caller
+//! that arrives while it is in flight waits for and receives a clone of the
+//! leader's result.
+
+use crate::backoff::Backoff;
+use crate::circuit::CircuitGuard;
+use crate::clock::Clock;
+use crate::condition::RetryCondition;
+use crate::Retry;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::future::{poll_fn, Future, IntoFuture};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+
+/// A coordinator that deduplicates concurrent retry runs by key.
+///
+/// Share it (via [`Arc`]) between every caller of the same operation, and
+/// attach it with [`Retry::with_single_flight()`]. Runs are only shared while
+/// they are in flight; a call made after a run has finished starts a new one.
+///
+/// If the leader is dropped before finishing, one of the waiting callers
+/// takes over and runs its own retry loop.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_retry::{Retry, SingleFlight, backoff::FixedDelay};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// # #[derive(Debug, Clone)]
+/// # struct MyError;
+/// # impl std::fmt::Display for MyError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+/// # }
+/// # async fn fetch_config() -> Result<String, MyError> { Ok(String::new()) }
+/// # async fn example() {
+/// let coordinator = Arc::new(SingleFlight::new());
+///
+/// // Concurrent calls with the same key share one retry run.
+/// let result = Retry::new(
+///     FixedDelay::new(Duration::from_millis(100)).take(3),
+///     move || async move { fetch_config().await }
+/// )
+/// .with_single_flight("config", coordinator.clone())
+/// .await;
+/// # }
+/// ```
+pub struct SingleFlight<T, E> {
+    calls: Mutex<HashMap<String, Arc<Call<T, E>>>>,
+}
+
+/// A run in flight, shared between its leader and the callers waiting on it.
+struct Call<T, E> {
+    state: Mutex<CallState<T, E>>,
+}
+
+struct CallState<T, E> {
+    result: Option<Result<T, E>>,
+    abandoned: bool,
+    wakers: Vec<Waker>,
+}
+
+/// The role a caller takes when joining a key.
+enum Role<'a, T, E> {
+    Leader(LeaderGuard<'a, T, E>),
+    Follower(Arc<Call<T, E>>),
+}
+
+/// Held by the leader while it runs; publishes the result or, if dropped
+/// early, lets a follower take over.
+struct LeaderGuard<'a, T, E> {
+    coordinator: &'a SingleFlight<T, E>,
+    key: &'a str,
+    call: Arc<Call<T, E>>,
+}
+
+impl<T, E> SingleFlight<T, E> {
+    /// Creates a new coordinator with no runs in flight.
+    pub fn new() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Becomes the leader for `key`, or follows the run already in flight.
+    fn join<'a>(&'a self, key: &'a str) -> Role<'a, T, E> {
+        let mut calls = self.calls.lock().unwrap();
+        if let Some(call) = calls.get(key) {
+            return Role::Follower(call.clone());
+        }
+        let call = Arc::new(Call {
+            state: Mutex::new(CallState {
+                result: None,
+                abandoned: false,
+                wakers: Vec::new(),
+            }),
+        });
+        calls.insert(key.to_owned(), call.clone());
+        Role::Leader(LeaderGuard {
+            coordinator: self,
+            key,
+            call,
+        })
+    }
+}
+
+impl<T, E> Default for SingleFlight<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E> fmt::Debug for SingleFlight<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let in_flight = self.calls.lock().unwrap().len();
+        f.debug_struct("SingleFlight")
+            .field("in_flight", &in_flight)
+            .finish()
+    }
+}
+
+impl<T, E> Call<T, E>
+where
+    T: Clone,
+    E: Clone,
+{
+    /// Waits for the leader's result, or `None` if the leader was dropped.
+    async fn wait(&self) -> Option<Result<T, E>> {
+        poll_fn(|cx| {
+            let mut state = self.state.lock().unwrap();
+            if let Some(result) = &state.result {
+                return Poll::Ready(Some(result.clone()));
+            }
+            if state.abandoned {
+                return Poll::Ready(None);
+            }
+            if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T, E> LeaderGuard<'_, T, E> {
+    /// Publishes the leader's result to every waiting caller.
+    fn complete(self, result: Result<T, E>) {
+        self.finish(Some(result));
+    }
+
+    fn finish(&self, result: Option<Result<T, E>>) {
+        // Stop new callers from joining before waking the current ones.
+        let mut calls = self.coordinator.calls.lock().unwrap();
+        if calls
+            .get(self.key)
+            .is_some_and(|call| Arc::ptr_eq(call, &self.call))
+        {
+            calls.remove(self.key);
+        }
+        drop(calls);
+
+        let mut state = self.call.state.lock().unwrap();
+        match result {
+            Some(result) => state.result = Some(result),
+            None => state.abandoned = true,
+        }
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl<T, E> Drop for LeaderGuard<'_, T, E> {
+    fn drop(&mut self) {
+        let finished = {
+            let state = self.call.state.lock().unwrap();
+            state.result.is_some() || state.abandoned
+        };
+        if !finished {
+            self.finish(None);
+        }
+    }
+}
+
+/// A [`Retry`] whose runs are shared between concurrent callers.
+///
+/// Created by [`Retry::with_single_flight()`]. The result type and error type
+/// must be `Clone`, since every caller receives its own copy of the result.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SingleFlightRetry<S, O, C, K, B, T, E>
+where
+    S: Backoff,
+{
+    pub(crate) inner: Retry<S, O, C, K, B>,
+    pub(crate) key: String,
+    pub(crate) coordinator: Arc<SingleFlight<T, E>>,
+}
+
+/// The core retry logic, implemented via `IntoFuture` with single-flight
+/// coordination around the retry loop.
+impl<S, O, C, K, B, F, T, E> IntoFuture for SingleFlightRetry<S, O, C, K, B, T, E>
+where
+    S: Backoff + Send + 'static,
+    K: Clock + Send + Sync + 'static,
+    B: CircuitGuard<E> + Send + 'static,
+    O: FnMut() -> F + Send + 'static,
+    C: RetryCondition<E> + Send + 'static,
+    F: Future<Output = Result<T, E>> + Send,
+    E: Display + Clone + Send + 'static,
+    T: Clone + Send + 'static,
+{
+    type Output = Result<T, E>;
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> <SingleFlightRetry<S, O, C, K, B, T, E> as IntoFuture>::IntoFuture {
+        Box::pin(async move {
+            let SingleFlightRetry {
+                inner,
+                key,
+                coordinator,
+            } = self;
+            loop {
+                match coordinator.join(&key) {
+                    Role::Leader(guard) => {
+                        let result = inner.execute().await.0;
+                        guard.complete(result.clone());
+                        return result;
+                    }
+                    Role::Follower(call) => {
+                        if let Some(result) = call.wait().await {
+                            return result;
+                        }
+                        // The leader was dropped; try to take over.
+                    }
+                }
+            }
+        })
+    }
+}
@@ -2,17 +2,21 @@
 
 //! Runtime-agnostic sleep functionality.
 //!
-//! This module provides a single `sleep` function that works with different async
-//! runtimes. The actual implementation is selected at compile time based on feature
-//! flags.
+//! This module provides a `sleep` function that works with different async
+//! runtimes, and a `timeout` function that races a future against the same
+//! runtime's timer. The actual implementation is selected at compile time based on
+//! feature flags. Everything that needs a timer goes through this module, so
+//! each feature works the same on every runtime.
 //!
 //! # Feature Flags
 //!
 //! You must enable exactly one of these features:
 //! - `tokio-timer` - Use Tokio's timer implementation
 //! - `async-std-timer` - Use async-std's timer implementation
+//! - `wasm-timer` - Use browser timers via `gloo-timers` (for `wasm32-unknown-unknown`)
 //!
-//! If no timer feature is enabled, you'll get a compile error with a helpful message.
+//! If no timer feature is enabled, the crate root emits a compile error listing
+//! the available features.
 //!
 //! # Design
 //!
@@ -20,6 +24,13 @@
 //! functionality in Rust async libraries. It has zero runtime cost - the compiler
 //! selects the correct implementation at build time.
 
+#[cfg(not(any(feature = "tokio-timer", feature = "async-std-timer")))]
+use std::future::poll_fn;
+use std::future::Future;
+#[cfg(not(any(feature = "tokio-timer", feature = "async-std-timer")))]
+use std::pin::pin;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
 /// Asynchronously sleeps for the specified duration.
@@ -35,7 +46,12 @@ use std::time::Duration;
 ///
 /// - With `tokio-timer`: Uses [`tokio::time::sleep`]
 /// - With `async-std-timer`: Uses [`async_std::task::sleep`]
-/// - With neither: Produces a compile error
+/// - With `wasm-timer`: Uses `gloo_timers::future::TimeoutFuture`
+/// - With none of these: The crate fails to compile (see the crate root)
+///
+/// A zero `duration` skips the timer entirely and yields to the executor once
+/// instead, so other tasks get a chance to run without the cost of a timer
+/// registration.
 ///
 /// # Examples
 ///
@@ -49,18 +65,181 @@ use std::time::Duration;
 /// # }
 /// ```
 pub async fn sleep(duration: Duration) {
+    if duration.is_zero() {
+        YieldNow(false).await;
+        return;
+    }
+
     // Use cfg_if for clean compile-time feature selection
     cfg_if::cfg_if! {
         if #[cfg(feature = "tokio-timer")] {
             tokio::time::sleep(duration).await;
         } else if #[cfg(feature = "async-std-timer")] {
             async_std::task::sleep(duration).await;
+        } else if #[cfg(feature = "wasm-timer")] {
+            // Browser timers take a `u32` of milliseconds; clamp rather than panic.
+            let millis = u32::try_from(duration.as_millis()).unwrap_or(u32::MAX);
+            WasmSleep(gloo_timers::future::TimeoutFuture::new(millis)).await;
         } else {
-            // Provide a helpful compile error if no timer feature is enabled
-            compile_error!(
-                "No async timer feature enabled. \
-                 Please enable either 'tokio-timer' or 'async-std-timer' in your Cargo.toml."
-            );
+            // Unreachable: the crate root refuses to compile without a timer feature.
+            let _ = duration;
+            unreachable!("no timer feature enabled");
+        }
+    }
+}
+
+/// The error returned by [`timeout()`] when the duration elapses first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Elapsed;
+
+/// Runs `future` to completion, unless `duration` elapses first.
+///
+/// Returns the future's output, or [`Elapsed`] if the timer fired first, in
+/// which case the future is dropped. The future is always polled before the
+/// timer, so one that is already ready wins even with a zero `duration`.
+///
+/// # Compile-Time Behavior
+///
+/// - With `tokio-timer`: Uses [`tokio::time::timeout`]
+/// - With `async-std-timer`: Uses [`async_std::future::timeout`]
+/// - With `wasm-timer`: Races the future against [`sleep()`]
+pub(crate) async fn timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "tokio-timer")] {
+            tokio::time::timeout(duration, future).await.map_err(|_| Elapsed)
+        } else if #[cfg(feature = "async-std-timer")] {
+            async_std::future::timeout(duration, future).await.map_err(|_| Elapsed)
+        } else {
+            race(duration, future).await
+        }
+    }
+}
+
+/// Races `future` against [`sleep()`], for runtimes without a timeout of
+/// their own.
+#[cfg(not(any(feature = "tokio-timer", feature = "async-std-timer")))]
+async fn race<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+    let mut future = pin!(future);
+    let mut timer = pin!(sleep(duration));
+
+    // Whichever finishes first wins; the other is dropped.
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+        if timer.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Err(Elapsed));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// A future that is pending exactly once, yielding control to the executor.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.0 {
+            return Poll::Ready(());
         }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Wraps a browser timer so it can be used where a `Send` future is required.
+///
+/// `TimeoutFuture` holds JavaScript handles and so is `!Send`, but the
+/// [`Clock`](crate::clock::Clock) trait requires `Send` sleep futures.
+#[cfg(all(
+    feature = "wasm-timer",
+    not(any(feature = "tokio-timer", feature = "async-std-timer"))
+))]
+struct WasmSleep(gloo_timers::future::TimeoutFuture);
+
+// SAFETY: `wasm32-unknown-unknown` is single-threaded, so the timer can never
+// actually be sent to (or polled from) another thread.
+#[cfg(all(
+    feature = "wasm-timer",
+    not(any(feature = "tokio-timer", feature = "async-std-timer"))
+))]
+unsafe impl Send for WasmSleep {}
+
+#[cfg(all(
+    feature = "wasm-timer",
+    not(any(feature = "tokio-timer", feature = "async-std-timer"))
+))]
+impl std::future::Future for WasmSleep {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        std::pin::Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn ready_after(delay: Duration) -> u32 {
+        sleep(delay).await;
+        42
+    }
+
+    #[cfg(feature = "tokio-timer")]
+    #[tokio::test]
+    async fn test_timeout_completes_on_tokio() {
+        let result = timeout(
+            Duration::from_millis(200),
+            ready_after(Duration::from_millis(10)),
+        );
+        assert_eq!(result.await, Ok(42));
+        // A ready future wins even against a zero timeout
+        assert_eq!(timeout(Duration::ZERO, async { 7 }).await, Ok(7));
+    }
+
+    #[cfg(feature = "tokio-timer")]
+    #[tokio::test]
+    async fn test_timeout_elapses_on_tokio() {
+        let result = timeout(
+            Duration::from_millis(10),
+            ready_after(Duration::from_secs(5)),
+        );
+        assert_eq!(result.await, Err(Elapsed));
+    }
+
+    #[cfg(feature = "async-std-timer")]
+    #[test]
+    fn test_timeout_completes_on_async_std() {
+        async_std::task::block_on(async {
+            let result = timeout(
+                Duration::from_millis(200),
+                ready_after(Duration::from_millis(10)),
+            );
+            assert_eq!(result.await, Ok(42));
+            assert_eq!(timeout(Duration::ZERO, async { 7 }).await, Ok(7));
+        });
+    }
+
+    #[cfg(feature = "async-std-timer")]
+    #[test]
+    fn test_timeout_elapses_on_async_std() {
+        async_std::task::block_on(async {
+            let result = timeout(
+                Duration::from_millis(10),
+                ready_after(Duration::from_secs(5)),
+            );
+            assert_eq!(result.await, Err(Elapsed));
+        });
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,123 @@
+// Author: Jacques Murray
+
+//! Hard deadlines for the whole retry loop and for individual attempts.
+
+use crate::backoff::Backoff;
+use crate::circuit::CircuitGuard;
+use crate::clock::Clock;
+use crate::condition::RetryCondition;
+use crate::Retry;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::future::{poll_fn, Future, IntoFuture};
+use std::pin::{pin, Pin};
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+/// The error returned when a [`HardTimeout`] expires before the retry loop
+/// finishes, or when an attempt outlives the budget set with
+/// [`Retry::with_strict_max_duration()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryTimeout {
+    timeout: Duration,
+}
+
+impl RetryTimeout {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+
+    /// Returns the timeout that expired.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl Display for RetryTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "retry timed out after {:?}", self.timeout)
+    }
+}
+
+impl Error for RetryTimeout {}
+
+/// A [`Retry`] that is cancelled if it has not finished within a timeout.
+///
+/// Created by [`Retry::with_hard_timeout()`]. Awaiting it returns
+/// `Ok(result)` with the retry's own result if the loop finishes in time, or
+/// `Err(RetryTimeout)` if the timeout fires first.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct HardTimeout<S, O, C, K, B>
+where
+    S: Backoff,
+{
+    pub(crate) inner: Retry<S, O, C, K, B>,
+    pub(crate) timeout: Duration,
+}
+
+/// The core retry logic, implemented via `IntoFuture` and raced against the
+/// timeout.
+impl<S, O, C, K, B, F, T, E> IntoFuture for HardTimeout<S, O, C, K, B>
+where
+    S: Backoff + Send + 'static,
+    K: Clock + Send + Sync + 'static,
+    B: CircuitGuard<E> + Send + 'static,
+    O: FnMut() -> F + Send + 'static,
+    C: RetryCondition<E> + Send + 'static,
+    F: Future<Output = Result<T, E>> + Send,
+    E: Display + Send,
+    T: Send,
+{
+    type Output = Result<Result<T, E>, RetryTimeout>;
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> <HardTimeout<S, O, C, K, B> as IntoFuture>::IntoFuture {
+        Box::pin(async move {
+            let HardTimeout { inner, timeout } = self;
+            match crate::sleep::timeout(timeout, inner.execute()).await {
+                Ok((result, _)) => Ok(result),
+                Err(_) => {
+                    #[cfg(feature = "logging")]
+                    log::error!(
+                        timeout_ms = timeout.as_millis() as u64;
+                        "Retry failed: hard timeout ({:?}) exceeded.",
+                        timeout
+                    );
+                    Err(RetryTimeout { timeout })
+                }
+            }
+        })
+    }
+}
+
+/// Runs `future` to completion, unless `clock` reaches `deadline` first.
+///
+/// Returns `None` if the timer fired first, in which case the future is
+/// dropped. The timer is only started once the future is pending, so one
+/// that is ready at once never sleeps on the clock.
+pub(crate) async fn within<K: Clock, F: Future>(
+    clock: &K,
+    deadline: Instant,
+    future: F,
+) -> Option<F::Output> {
+    let mut future = pin!(future);
+    let mut timer = pin!(None);
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        if timer.is_none() {
+            timer.set(Some(
+                clock.sleep(deadline.saturating_duration_since(clock.now())),
+            ));
+        }
+        match timer.as_mut().as_pin_mut().map(|timer| timer.poll(cx)) {
+            Some(Poll::Ready(())) => Poll::Ready(None),
+            _ => Poll::Pending,
+        }
+    })
+    .await
+}
@@ -0,0 +1,114 @@
+// Author: Jacques Murray
+
+//! Treating panics in the operation as retryable errors.
+//!
+//! Some third-party async code panics on transient failures. With
+//! [`Retry::with_catch_unwind()`](crate::Retry::with_catch_unwind), each
+//! invocation is run under [`std::panic::catch_unwind`] and a caught panic is
+//! converted into the operation's error type via `From<PanicError>`.
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The error produced when an operation panics under
+/// [`Retry::with_catch_unwind()`](crate::Retry::with_catch_unwind).
+///
+/// It carries the panic message when the payload was a string (as it is for
+/// `panic!("...")`), so the error type it is converted into can report it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicError {
+    message: Option<String>,
+}
+
+impl PanicError {
+    fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => Some(*message),
+            Err(payload) => payload.downcast_ref::<&str>().map(|s| s.to_string()),
+        };
+        Self { message }
+    }
+
+    /// Returns the panic message, if the payload was a string.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl fmt::Display for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "operation panicked: {}", message),
+            None => write!(f, "operation panicked"),
+        }
+    }
+}
+
+impl Error for PanicError {}
+
+/// A future that converts a panic in the wrapped operation future into an error.
+///
+/// Produced by the operation of a `Retry` configured with
+/// [`with_catch_unwind()`](crate::Retry::with_catch_unwind).
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CatchUnwind<F> {
+    state: State<F>,
+}
+
+enum State<F> {
+    Running(Pin<Box<F>>),
+    Panicked(Option<PanicError>),
+}
+
+impl<F> fmt::Debug for CatchUnwind<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let panicked = matches!(self.state, State::Panicked(_));
+        f.debug_struct("CatchUnwind")
+            .field("panicked", &panicked)
+            .finish()
+    }
+}
+
+/// Calls `operation` and wraps the returned future, catching a panic from
+/// either the call itself or any poll of the future.
+pub(crate) fn call<O, F>(operation: &mut O) -> CatchUnwind<F>
+where
+    O: FnMut() -> F,
+{
+    let state = match catch_unwind(AssertUnwindSafe(&mut *operation)) {
+        Ok(future) => State::Running(Box::pin(future)),
+        Err(payload) => State::Panicked(Some(PanicError::from_payload(payload))),
+    };
+    CatchUnwind { state }
+}
+
+impl<F, T, E> Future for CatchUnwind<F>
+where
+    F: Future<Output = Result<T, E>>,
+    E: From<PanicError>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = match &mut self.state {
+            State::Running(future) => future,
+            State::Panicked(error) => {
+                let error = error.take().expect("CatchUnwind polled after completion");
+                return Poll::Ready(Err(error.into()));
+            }
+        };
+        match catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(poll) => poll,
+            Err(payload) => {
+                // A panicked future must not be polled again.
+                self.state = State::Panicked(None);
+                Poll::Ready(Err(PanicError::from_payload(payload).into()))
+            }
+        }
+    }
+}
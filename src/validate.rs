@@ -0,0 +1,129 @@
+// Author: Jacques Murray
+
+//! Operations whose successful values are validated before being accepted.
+
+use crate::backoff::Backoff;
+use crate::circuit::CircuitGuard;
+use crate::clock::Clock;
+use crate::condition::RetryCondition;
+use crate::Retry;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+
+/// The error returned when the retry loop gives up after a value rejected by
+/// [`Retry::with_validator()`], and no attempt failed with an error of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidValue {
+    attempt: usize,
+}
+
+impl InvalidValue {
+    pub(crate) fn new(attempt: usize) -> Self {
+        Self { attempt }
+    }
+
+    /// Returns the attempt that produced the rejected value.
+    pub fn attempt(&self) -> usize {
+        self.attempt
+    }
+}
+
+impl Display for InvalidValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid value returned on attempt {}", self.attempt)
+    }
+}
+
+impl Error for InvalidValue {}
+
+/// Decides whether the retry loop accepts an `Ok` value.
+///
+/// This is implemented for every `FnMut(&T) -> bool` closure, which reports
+/// a rejected value as is if the loop gives up on it.
+pub(crate) trait Validator<T, E> {
+    /// Returns `true` if `value` ends the retry loop.
+    fn accept(&mut self, value: &T) -> bool;
+
+    /// Returns the result to report for a `value` rejected on `attempt`,
+    /// should the loop give up on it.
+    fn reject(&mut self, value: T, attempt: usize) -> Result<T, E>;
+}
+
+impl<T, E, F> Validator<T, E> for F
+where
+    F: FnMut(&T) -> bool,
+{
+    fn accept(&mut self, value: &T) -> bool {
+        self(value)
+    }
+
+    fn reject(&mut self, value: T, _attempt: usize) -> Result<T, E> {
+        Ok(value)
+    }
+}
+
+/// A validator that reports rejected values as [`InvalidValue`] errors.
+struct RejectInvalid<V>(V);
+
+impl<T, E, V> Validator<T, E> for RejectInvalid<V>
+where
+    V: FnMut(&T) -> bool,
+    E: From<InvalidValue>,
+{
+    fn accept(&mut self, value: &T) -> bool {
+        (self.0)(value)
+    }
+
+    fn reject(&mut self, _value: T, attempt: usize) -> Result<T, E> {
+        Err(InvalidValue::new(attempt).into())
+    }
+}
+
+/// An operation whose `Ok` values must pass a validator to end the retry loop.
+///
+/// Created by [`Retry::with_validator()`].
+#[derive(Debug, Clone)]
+pub struct Validated<Op, V> {
+    pub(crate) operation: Op,
+    pub(crate) validator: V,
+}
+
+/// The core retry logic, implemented via `IntoFuture` for operations with a
+/// validator.
+impl<S, Op, V, C, K, B, F, T, E> IntoFuture for Retry<S, Validated<Op, V>, C, K, B>
+where
+    S: Backoff + Send + 'static,
+    K: Clock + Send + Sync + 'static,
+    B: CircuitGuard<E> + Send + 'static,
+    Op: FnMut() -> F + Send + 'static,
+    V: FnMut(&T) -> bool + Send + 'static,
+    C: RetryCondition<E> + Send + 'static,
+    F: Future<Output = Result<T, E>> + Send,
+    E: From<InvalidValue> + Display + Send,
+    T: Send,
+{
+    type Output = Result<T, E>;
+
+    // We box the future to avoid complex type signatures in the return.
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> <Retry<S, Validated<Op, V>, C, K, B> as IntoFuture>::IntoFuture {
+        Box::pin(async move {
+            let Retry {
+                mut strategy,
+                operation:
+                    Validated {
+                        mut operation,
+                        validator,
+                    },
+                mut config,
+            } = self;
+            config
+                .execute(&mut strategy, &mut operation, &mut RejectInvalid(validator))
+                .await
+                .0
+        })
+    }
+}
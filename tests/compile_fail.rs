@@ -0,0 +1,10 @@
+// Author: Jacques Murray
+//
+// Compile-fail tests for the builder. Each file in `tests/ui` misuses the API
+// and must fail to build with the error recorded next to it.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,42 @@
+// Author: Jacques Murray
+//
+// Tests for the `global-metrics` feature. These live in their own test
+// binary, so no other test's retries are counted while they run.
+
+#![cfg(all(feature = "global-metrics", feature = "tokio-timer"))]
+
+use async_retry::{backoff::FixedDelay, metrics, Retry};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_global_counters_track_attempts() {
+    let attempts_before = metrics::total_attempts();
+    let retries_before = metrics::total_retries();
+
+    // Succeeds on the third attempt: 3 attempts, 2 of them retries
+    let calls = Arc::new(AtomicU32::new(0));
+    let calls_clone = calls.clone();
+    let result = Retry::new(FixedDelay::new(Duration::from_millis(10)).take(5), move || {
+        let calls = calls_clone.clone();
+        async move {
+            match calls.fetch_add(1, Ordering::SeqCst) + 1 {
+                3 => Ok(()),
+                _ => Err("fail"),
+            }
+        }
+    })
+        .await;
+    assert_eq!(result, Ok(()));
+
+    // Succeeds immediately: 1 attempt, no retries
+    let result = Retry::new(FixedDelay::new(Duration::from_millis(10)).take(5), || async {
+        Ok::<_, &str>(())
+    })
+        .await;
+    assert_eq!(result, Ok(()));
+
+    assert_eq!(metrics::total_attempts() - attempts_before, 4);
+    assert_eq!(metrics::total_retries() - retries_before, 2);
+}
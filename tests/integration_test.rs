@@ -106,6 +106,44 @@ async fn test_failure_on_max_retries() {
     assert!(elapsed >= Duration::from_millis(30));
 }
 
+#[tokio::test]
+async fn test_max_attempts_counts_initial_attempt() {
+    // Strategy limits count retries: 3 retries = 4 attempts
+    let op = Op::new(10, "fail");
+    let op_clone = op.clone();
+    let result = Retry::new(FixedDelay::new(Duration::from_millis(1)).take(3), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(op.attempts(), 4);
+
+    // with_max_attempts counts the initial attempt: 3 attempts = 2 retries
+    let op = Op::new(10, "fail");
+    let op_clone = op.clone();
+    let result = Retry::new(FixedDelay::new(Duration::from_millis(1)).take(3), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_attempts(3)
+        .await;
+    assert_eq!(result, Err(TestError("fail".to_string())));
+    assert_eq!(op.attempts(), 3);
+
+    // The first attempt always runs
+    let op = Op::new(10, "fail");
+    let op_clone = op.clone();
+    let result = Retry::new(FixedDelay::new(Duration::from_millis(1)), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_attempts(0)
+        .await;
+    assert!(result.is_err());
+    assert_eq!(op.attempts(), 1);
+}
+
 #[tokio::test]
 async fn test_failure_on_max_duration() {
     // Max Duration
@@ -128,6 +166,57 @@ async fn test_failure_on_max_duration() {
     assert_eq!(op.attempts(), 2);
 }
 
+#[tokio::test]
+async fn test_max_duration_shorter_than_first_delay() {
+    let strategy = || FixedDelay::new(Duration::from_millis(100)).take(5);
+
+    // The operation still runs once, but the 100ms delay doesn't fit in 50ms
+    let op = Op::new(2, "fail");
+    let op_clone = op.clone();
+    let start = Instant::now();
+    let result = Retry::new(strategy(), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_duration(Duration::from_millis(50))
+        .await;
+    assert_eq!(result, Err(TestError("fail".to_string())));
+    assert_eq!(op.attempts(), 1);
+    assert!(start.elapsed() < Duration::from_millis(50));
+
+    // With min_one_retry, the delay is shortened and the second attempt runs
+    let op = Op::new(2, "fail");
+    let op_clone = op.clone();
+    let start = Instant::now();
+    let result = Retry::new(strategy(), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_duration(Duration::from_millis(50))
+        .with_min_one_retry()
+        .await;
+    assert_eq!(result, Ok(2));
+    assert_eq!(op.attempts(), 2);
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    // With no budget left after the first attempt, there is no retry at all
+    let attempts = Arc::new(AtomicU32::new(0));
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy(), move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(60)).await;
+            Err::<(), _>(TestError("slow".to_string()))
+        }
+    })
+        .with_max_duration(Duration::from_millis(50))
+        .with_min_one_retry()
+        .await;
+    assert_eq!(result, Err(TestError("slow".to_string())));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
+
 #[tokio::test]
 async fn test_failure_on_condition() {
     // Retry Conditions
@@ -148,4 +237,2416 @@ async fn test_failure_on_condition() {
     assert_eq!(result.unwrap_err(), TestError("PERMANENT".to_string()));
     // Should fail on the very first attempt
     assert_eq!(op.attempts(), 1);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_rejected_error_does_not_advance_strategy() {
+    // Fails with "transient" twice, then with "PERMANENT"
+    let attempts = Arc::new(AtomicU32::new(0));
+    let delays_taken = Arc::new(AtomicU32::new(0));
+
+    let delays_clone = delays_taken.clone();
+    let strategy = FixedDelay::new(Duration::from_millis(10))
+        .take(5)
+        .inspect(move |_| {
+            delays_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            match attempts.fetch_add(1, Ordering::SeqCst) + 1 {
+                1 | 2 => Err(TestError("transient".to_string())),
+                _ => Err::<(), _>(TestError("PERMANENT".to_string())),
+            }
+        }
+    })
+        .with_condition(|e: &TestError| e.0 != "PERMANENT")
+        .await;
+
+    assert_eq!(result, Err(TestError("PERMANENT".to_string())));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    // Only the two retried errors consumed a delay; the rejected one didn't
+    assert_eq!(delays_taken.load(Ordering::SeqCst), 2);
+}
+
+// An error type that classifies itself via the Retryable trait
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum ClassifiedError {
+    Transient,
+    Permanent,
+}
+
+impl std::fmt::Display for ClassifiedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl std::error::Error for ClassifiedError {}
+
+impl async_retry::Retryable for ClassifiedError {
+    fn is_retryable(&self) -> bool {
+        matches!(self, ClassifiedError::Transient)
+    }
+}
+
+#[tokio::test]
+async fn test_retryable_trait() {
+    // Fails transiently twice, then permanently
+    let attempts = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let attempts_clone = attempts.clone();
+    let result: Result<(), _> = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(ClassifiedError::Transient)
+            } else {
+                Err(ClassifiedError::Permanent)
+            }
+        }
+    })
+        .with_retryable_trait()
+        .await;
+
+    assert_eq!(result.unwrap_err(), ClassifiedError::Permanent);
+    // Two transient failures are retried, the permanent one is not
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_exponential_schedule_with_test_clock() {
+    use async_retry::{backoff::ExponentialBackoff, TestClock};
+
+    let op = Op::new(10, "fail"); // Never succeeds within the schedule
+    let strategy = ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(5);
+    let clock = TestClock::new();
+
+    let start = Instant::now();
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_clock(clock.clone())
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(op.attempts(), 6);
+    // 1s + 2s + 4s + 8s + 16s of virtual time
+    assert_eq!(clock.elapsed(), Duration::from_secs(31));
+    // ...without actually waiting for it
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_max_duration_with_test_clock() {
+    use async_retry::TestClock;
+
+    let op = Op::new(10, "fail");
+    let strategy = FixedDelay::new(Duration::from_secs(50)).take(10);
+    let clock = TestClock::new();
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_duration(Duration::from_secs(75))
+        .with_clock(clock.clone())
+        .await;
+
+    assert!(result.is_err());
+    // Same schedule as `test_failure_on_max_duration`, in virtual seconds
+    assert_eq!(op.attempts(), 2);
+    assert_eq!(clock.elapsed(), Duration::from_secs(50));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_timeout_error_mapping() {
+    use async_retry::TestClock;
+
+    let op = Op::new(10, "connection refused");
+    let strategy = FixedDelay::new(Duration::from_secs(50)).take(10);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_duration(Duration::from_secs(75))
+        .with_timeout_error(|last: TestError| TestError(format!("deadline exceeded: {}", last)))
+        .with_clock(TestClock::new())
+        .await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        TestError("deadline exceeded: connection refused".to_string())
+    );
+    assert_eq!(op.attempts(), 2);
+}
+
+#[tokio::test]
+async fn test_timeout_error_not_applied_when_exhausted() {
+    let op = Op::new(10, "connection refused");
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(2);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_duration(Duration::from_secs(60))
+        .with_timeout_error(|_: TestError| TestError("deadline exceeded".to_string()))
+        .await;
+
+    assert_eq!(result.unwrap_err(), TestError("connection refused".to_string()));
+    assert_eq!(op.attempts(), 3);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_timeout_error_not_applied_to_non_retryable_error() {
+    use async_retry::TestClock;
+
+    let clock = TestClock::new();
+    let op_clock = clock.clone();
+    let strategy = FixedDelay::new(Duration::from_secs(1)).take(10);
+
+    // The non-retryable error arrives after the deadline has passed
+    let result = Retry::new(strategy, move || {
+        let clock = op_clock.clone();
+        async move {
+            clock.advance(Duration::from_secs(100));
+            Err::<(), _>(TestError("bad request".to_string()))
+        }
+    })
+        .with_condition(|e: &TestError| e.0 != "bad request")
+        .with_max_duration(Duration::from_secs(60))
+        .with_timeout_error(|_: TestError| TestError("deadline exceeded".to_string()))
+        .with_clock(clock)
+        .await;
+
+    assert_eq!(result.unwrap_err(), TestError("bad request".to_string()));
+}
+
+#[tokio::test]
+async fn test_zero_delay_then_ramp_up() {
+    let op = Op::new(3, "fail"); // Fails twice
+    let strategy = vec![Duration::ZERO, Duration::from_millis(10)].into_iter();
+
+    let start = Instant::now();
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    }).await;
+
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(op.attempts(), 3);
+    assert!(start.elapsed() >= Duration::from_millis(10));
+}
+
+#[cfg(all(feature = "jitter", feature = "test-util"))]
+#[tokio::test]
+async fn test_startup_splay_delays_first_attempt() {
+    use async_retry::TestClock;
+
+    let splay = Duration::from_secs(5);
+    for _ in 0..10 {
+        let clock = TestClock::new();
+        let first_attempt_at = Arc::new(std::sync::Mutex::new(None));
+
+        let (clock_clone, first_clone) = (clock.clone(), first_attempt_at.clone());
+        let result = Retry::new(FixedDelay::new(Duration::from_secs(1)).take(3), || async {
+            Ok::<(), TestError>(())
+        })
+            .with_startup_splay(splay)
+            .with_before_attempt(move |_| {
+                first_clone.lock().unwrap().get_or_insert(clock_clone.elapsed());
+            })
+            .with_clock(clock.clone())
+            .await;
+
+        assert!(result.is_ok());
+        let delayed_by = first_attempt_at.lock().unwrap().unwrap();
+        assert!(delayed_by <= splay);
+        assert_eq!(clock.elapsed(), delayed_by);
+    }
+}
+
+#[cfg(all(feature = "jitter", feature = "test-util"))]
+#[tokio::test]
+async fn test_startup_splay_drawn_from_source() {
+    use async_retry::{JitterSource, SeededSource, TestClock};
+
+    let splay = Duration::from_secs(5);
+    let expected = SeededSource::new(42).next_duration(Duration::ZERO, splay);
+
+    let clock = TestClock::new();
+    let result = Retry::new(FixedDelay::new(Duration::from_secs(1)).take(3), || async {
+        Ok::<(), TestError>(())
+    })
+        .with_startup_splay(splay)
+        .with_splay_source(SeededSource::new(42))
+        .with_clock(clock.clone())
+        .await;
+
+    assert!(result.is_ok());
+    assert_eq!(clock.elapsed(), expected);
+}
+
+#[tokio::test]
+async fn test_hedged_attempt_wins() {
+    // The first invocation is slow, the hedged second one is fast
+    let invocations = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(3);
+
+    let start = Instant::now();
+    let invocations_clone = invocations.clone();
+    let result: Result<&str, TestError> = Retry::new(strategy, move || {
+        let invocations = invocations_clone.clone();
+        async move {
+            if invocations.fetch_add(1, Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                Ok("slow")
+            } else {
+                Ok("fast")
+            }
+        }
+    })
+        .with_hedging(Duration::from_millis(20))
+        .await;
+
+    assert_eq!(result.unwrap(), "fast");
+    assert_eq!(invocations.load(Ordering::SeqCst), 2);
+    assert!(start.elapsed() < Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn test_run_with_attempts() {
+    let op = Op::new(3, "fail"); // Succeeds on attempt 3
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .run_with_attempts()
+        .await;
+
+    assert_eq!(result.unwrap(), (3, 3));
+}
+
+#[tokio::test]
+async fn test_boxed_error() {
+    type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+    let attempts = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err::<u32, BoxError>(Box::new(TestError("boxed".to_string())))
+            } else {
+                Ok(42)
+            }
+        }
+    })
+        .with_condition(|e: &BoxError| e.to_string() == "boxed")
+        .await;
+
+    assert_eq!(result.unwrap(), 42);
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_with_state() {
+    let op = Arc::new(Op::new(3, "fail")); // Fails twice
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let result = Retry::with_state(strategy, op.clone(), |op| async move { op.run().await }).await;
+
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(op.attempts(), 3);
+}
+
+#[tokio::test]
+async fn test_attempt_counter_visible_while_running() {
+    use std::sync::atomic::AtomicUsize;
+    use tokio::sync::Notify;
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let release = Arc::new(Notify::new());
+    let calls = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let (release_clone, calls_clone) = (release.clone(), calls.clone());
+    let retry = Retry::new(strategy, move || {
+            let (release, calls) = (release_clone.clone(), calls_clone.clone());
+        async move {
+            // The first attempt fails; the second waits to be released
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(TestError("fail".to_string()));
+            }
+            release.notified().await;
+            Ok(())
+        }
+    })
+        .with_attempt_counter(counter.clone());
+    let handle = tokio::spawn(async move { retry.await });
+
+    while counter.load(Ordering::SeqCst) < 2 {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+    release.notify_one();
+
+    handle.await.unwrap().unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_or_condition() {
+    use async_retry::condition::{not, or, retry_none};
+
+    let is_timeout = |e: &TestError| e.0 == "timeout";
+    let is_busy = |e: &TestError| e.0 == "busy";
+
+    // Fails with "timeout", then "busy", then "fatal"
+    let attempts = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            let message = match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 => "timeout",
+                1 => "busy",
+                _ => "fatal",
+            };
+            Err::<u32, TestError>(TestError(message.to_string()))
+        }
+    })
+        .with_condition(or(is_timeout, is_busy))
+        .await;
+
+    assert_eq!(result.unwrap_err(), TestError("fatal".to_string()));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    let mut condition = not(retry_none::<TestError>());
+    assert!(condition(&TestError("any".to_string())));
+}
+
+#[tokio::test]
+async fn test_on_type_condition_with_boxed_error() {
+    use async_retry::condition;
+    type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+    // Retries while the error is a TestError, then stops on an io::Error
+    let attempts = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err::<u32, BoxError>(Box::new(TestError("boxed".to_string())))
+            } else {
+                Err(Box::new(std::io::Error::other("io")) as BoxError)
+            }
+        }
+    })
+        .with_condition(condition::on_type::<TestError, _>())
+        .await;
+
+    assert!(result.unwrap_err().is::<std::io::Error>());
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_io_timeout_condition_with_boxed_error() {
+    use async_retry::condition;
+    use std::io::{Error, ErrorKind};
+    type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+    let attempts = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            let kind = match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => ErrorKind::TimedOut,
+                _ => ErrorKind::PermissionDenied,
+            };
+            Err::<u32, BoxError>(Box::new(Error::from(kind)))
+        }
+    })
+        .with_condition(condition::io_timeout())
+        .await;
+
+    let error = result.unwrap_err();
+    let io_error = error.downcast_ref::<Error>().unwrap();
+    assert_eq!(io_error.kind(), ErrorKind::PermissionDenied);
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_local_retry_with_non_send_operation() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    // Rc makes the operation (and its future) !Send
+    let attempts = Rc::new(Cell::new(0u32));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(TestError("fail".to_string()))
+            } else {
+                Ok(attempts.get())
+            }
+        }
+    })
+        .local()
+        .await;
+
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(attempts.get(), 3);
+}
+
+#[tokio::test]
+async fn test_before_attempt_hook() {
+    let op = Op::new(3, "fail"); // Succeeds on attempt 3
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+    let observed = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let op_clone = op.clone();
+    let observed_clone = observed.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_before_attempt(move |attempt| observed_clone.lock().unwrap().push(attempt))
+        .await;
+
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(*observed.lock().unwrap(), vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_default_policy() {
+    let op = Op::new(3, "fail"); // Succeeds on attempt 3
+
+    let op_clone = op.clone();
+    let result = Retry::default_policy(move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    }).await;
+
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(op.attempts(), 3);
+}
+
+impl From<async_retry::circuit::CircuitOpenError> for TestError {
+    fn from(e: async_retry::circuit::CircuitOpenError) -> Self {
+        TestError(e.to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_fails_fast() {
+    use async_retry::circuit::{CircuitBreaker, CircuitState};
+
+    let breaker = Arc::new(CircuitBreaker::new(3, Duration::from_secs(60)));
+
+    // The first retry exhausts its attempts and opens the circuit
+    let op = Op::new(10, "fail");
+    let op_clone = op.clone();
+    let result = Retry::new(FixedDelay::new(Duration::from_millis(10)).take(2), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_circuit_breaker(breaker.clone())
+        .await;
+
+    assert_eq!(result.unwrap_err(), TestError("fail".to_string()));
+    assert_eq!(op.attempts(), 3);
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    // The next retry fails fast without calling the operation
+    let op = Op::new(1, "fail");
+    let op_clone = op.clone();
+    let result = Retry::new(FixedDelay::new(Duration::from_millis(10)).take(2), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_circuit_breaker(breaker.clone())
+        .await;
+
+    assert_eq!(result.unwrap_err(), TestError("circuit breaker is open".to_string()));
+    assert_eq!(op.attempts(), 0);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_reuse_configured_retry() {
+    use async_retry::{backoff::ExponentialBackoff, TestClock};
+
+    let clock = TestClock::new();
+    let mut retry = Retry::new(
+        ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(5),
+        (),
+    )
+        .with_clock(clock.clone());
+
+    // Fails twice: sleeps 1s + 2s
+    let op = Op::new(3, "fail");
+    let op_clone = op.clone();
+    let result = retry.run_with(move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    }).await;
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(clock.elapsed(), Duration::from_secs(3));
+
+    // Fails twice again: the backoff starts fresh, so it also sleeps 1s + 2s
+    let op = Op::new(3, "fail");
+    let op_clone = op.clone();
+    let result = retry.run_with(move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    }).await;
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(clock.elapsed(), Duration::from_secs(6));
+}
+
+// A strategy that records every delay it yields
+#[derive(Clone)]
+struct RecordingBackoff {
+    inner: async_retry::backoff::ExponentialBackoff,
+    delays: Arc<std::sync::Mutex<Vec<Duration>>>,
+}
+
+impl Iterator for RecordingBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let delay = self.inner.next()?;
+        self.delays.lock().unwrap().push(delay);
+        Some(delay)
+    }
+}
+
+#[tokio::test]
+async fn test_consecutive_runs_start_at_base_delay() {
+    let delays = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let strategy = RecordingBackoff {
+        inner: async_retry::backoff::ExponentialBackoff::new(Duration::from_millis(1)),
+        delays: delays.clone(),
+    };
+    let mut retry = Retry::new(strategy, ());
+
+    for _ in 0..2 {
+        let op = Op::new(3, "fail"); // Fails twice per run
+        let result = retry.run_with(move || {
+            let op = op.clone();
+            async move { op.run().await }
+        }).await;
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    let ms = Duration::from_millis;
+    assert_eq!(*delays.lock().unwrap(), vec![ms(1), ms(2), ms(1), ms(2)]);
+}
+
+// A logger that records the structured key-values of every log record
+#[cfg(feature = "logging")]
+mod capture {
+    use log::kv::{Key, Value, VisitSource};
+    use std::collections::HashMap;
+    use std::sync::{Mutex, OnceLock};
+
+    pub type Fields = HashMap<String, String>;
+
+    struct CapturingLogger {
+        records: Mutex<Vec<Fields>>,
+    }
+
+    struct Collect<'a>(&'a mut Fields);
+
+    impl<'kvs> VisitSource<'kvs> for Collect<'_> {
+        fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), log::kv::Error> {
+            self.0.insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+    }
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let mut fields = Fields::new();
+            record.key_values().visit(&mut Collect(&mut fields)).unwrap();
+            // Not a structured field, but recorded alongside them for tests
+            fields.insert("level".to_string(), record.level().to_string());
+            self.records.lock().unwrap().push(fields);
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn logger() -> &'static CapturingLogger {
+        static LOGGER: OnceLock<&'static CapturingLogger> = OnceLock::new();
+        LOGGER.get_or_init(|| {
+            let logger = Box::leak(Box::new(CapturingLogger { records: Mutex::new(Vec::new()) }));
+            log::set_logger(logger).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+            logger
+        })
+    }
+
+    // Installs the logger (once) and returns the records for one operation name
+    pub fn records_for(operation_name: &str) -> Vec<Fields> {
+        logger()
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|fields| fields.get("operation_name").map(String::as_str) == Some(operation_name))
+            .cloned()
+            .collect()
+    }
+
+    pub fn install() {
+        logger();
+    }
+}
+
+#[cfg(feature = "logging")]
+#[tokio::test]
+async fn test_structured_log_fields() {
+    capture::install();
+
+    let op = Op::new(2, "boom"); // Fails once
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+    .with_operation_name("structured_fields")
+    .await;
+    assert_eq!(result.unwrap(), 2);
+
+    let records = capture::records_for("structured_fields");
+    let failure = records
+        .iter()
+        .find(|fields| fields.contains_key("error_display"))
+        .expect("failure record with structured fields");
+    assert_eq!(failure["attempt"], "1");
+    assert_eq!(failure["error_display"], "boom");
+
+    let retrying = records
+        .iter()
+        .find(|fields| fields.contains_key("delay_ms"))
+        .expect("retry record with structured fields");
+    assert_eq!(retrying["delay_ms"], "10");
+}
+
+#[tokio::test]
+async fn test_max_retries_zero_is_single_attempt() {
+    use async_retry::backoff::ExponentialBackoff;
+
+    let op = Op::new(2, "fail"); // Would succeed on a retry
+    let strategy = ExponentialBackoff::new(Duration::from_millis(10)).with_max_retries(0);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    }).await;
+
+    assert_eq!(result.unwrap_err(), TestError("fail".to_string()));
+    assert_eq!(op.attempts(), 1);
+}
+
+#[tokio::test]
+async fn test_boxed_strategies() {
+    use async_retry::backoff::{Backoff, ExponentialBackoff, FibonacciBackoff};
+
+    let strategies: Vec<Box<dyn Backoff + Send>> = vec![
+        FixedDelay::new(Duration::from_millis(1)).take(3).boxed(),
+        ExponentialBackoff::new(Duration::from_millis(1)).take(3).boxed(),
+        FibonacciBackoff::new(Duration::from_millis(1)).take(3).boxed(),
+    ];
+
+    for strategy in strategies {
+        let op = Op::new(3, "fail"); // Fails twice
+        let op_clone = op.clone();
+        let result = Retry::new(strategy, move || {
+            let op = op_clone.clone();
+            async move { op.run().await }
+        }).await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(op.attempts(), 3);
+    }
+}
+
+#[tokio::test]
+async fn test_record_delays() {
+    let observed = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let strategy = RecordingBackoff {
+        inner: async_retry::backoff::ExponentialBackoff::new(Duration::from_millis(1)).with_max_retries(3),
+        delays: observed.clone(),
+    };
+
+    let op = Op::new(10, "fail"); // Never succeeds within the strategy
+    let op_clone = op.clone();
+    let (result, stats) = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_record_delays()
+        .run_with_stats()
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(stats.attempts, 4);
+    assert_eq!(stats.delays.as_ref(), Some(&*observed.lock().unwrap()));
+    assert_eq!(observed.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_single_flight_shares_one_run() {
+    use async_retry::SingleFlight;
+
+    let coordinator = Arc::new(SingleFlight::new());
+    let executions = Arc::new(AtomicU32::new(0));
+
+    let mut handles = Vec::new();
+    for _ in 0..10 {
+        let executions = executions.clone();
+        let retry = Retry::new(FixedDelay::new(Duration::from_millis(10)).take(3), move || {
+            let executions = executions.clone();
+            async move {
+                executions.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok::<u32, TestError>(42)
+            }
+        })
+            .with_single_flight("shared", coordinator.clone());
+        handles.push(tokio::spawn(async move { retry.await }));
+    }
+
+    for handle in handles {
+        assert_eq!(handle.await.unwrap().unwrap(), 42);
+    }
+    assert_eq!(executions.load(Ordering::SeqCst), 1);
+}
+
+#[cfg(feature = "tokio-timer")]
+#[tokio::test]
+async fn test_spawn_returns_handle() {
+    let op = Op::new(3, "fail"); // Fails twice
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let op_clone = op.clone();
+    let handle = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    }).spawn();
+
+    assert_eq!(handle.await.unwrap().unwrap(), 3);
+    assert_eq!(op.attempts(), 3);
+}
+
+static ASYNC_FN_CALLS: AtomicU32 = AtomicU32::new(0);
+
+// A bare async fn with no captures, passed to `Retry::new` by name
+async fn flaky_async_fn() -> Result<u32, TestError> {
+    let current = ASYNC_FN_CALLS.fetch_add(1, Ordering::SeqCst) + 1;
+    if current < 3 {
+        Err(TestError("fail".to_string()))
+    } else {
+        Ok(current)
+    }
+}
+
+async fn always_ok_async_fn() -> Result<&'static str, TestError> {
+    Ok("ok")
+}
+
+#[tokio::test]
+async fn test_async_fn_item_as_operation() {
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+    let result = Retry::new(strategy, flaky_async_fn).await;
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(ASYNC_FN_CALLS.load(Ordering::SeqCst), 3);
+
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+    let result = Retry::new(strategy, always_ok_async_fn)
+        .with_condition(|_: &TestError| false)
+        .await;
+    assert_eq!(result.unwrap(), "ok");
+}
+
+#[tokio::test]
+async fn test_abort_flag_interrupts_backoff() {
+    use std::sync::atomic::AtomicBool;
+
+    let op = Op::new(10, "fail");
+    let strategy = FixedDelay::new(Duration::from_secs(10)).take(5);
+    let abort = Arc::new(AtomicBool::new(false));
+
+    let abort_clone = abort.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        abort_clone.store(true, Ordering::SeqCst);
+    });
+
+    let start = Instant::now();
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_abort_flag(abort)
+        .await;
+
+    // Returned the last error well before the 10s backoff would have ended
+    assert_eq!(result.unwrap_err(), TestError("fail".to_string()));
+    assert_eq!(op.attempts(), 1);
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_retry_macro() {
+    // Captures must be `Copy`, so share the operation as a `&'static`
+    let op: &'static Op = Box::leak(Box::new(Op::new(3, "fail"))); // Fails twice
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let result = async_retry::retry!(strategy, { op.run().await });
+
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(op.attempts(), 3);
+}
+
+#[tokio::test]
+async fn test_retry_macro_with_condition() {
+    let op: &'static Op = Box::leak(Box::new(Op::new(3, "fatal"))); // Would succeed on attempt 3
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let result = async_retry::retry!(
+        strategy,
+        { op.run().await },
+        condition = |e: &TestError| e.0 != "fatal",
+        max_duration = Duration::from_secs(5),
+    );
+
+    assert_eq!(result.unwrap_err(), TestError("fatal".to_string()));
+    assert_eq!(op.attempts(), 1);
+}
+
+#[tokio::test]
+async fn test_with_context_persists_across_attempts() {
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let (result, attempts) = Retry::with_context(strategy, Vec::new(), |attempts: &mut Vec<usize>| {
+        attempts.push(attempts.len() + 1);
+        let attempt = attempts.len();
+        async move {
+            if attempt < 3 {
+                Err(TestError("fail".to_string()))
+            } else {
+                Ok(attempt)
+            }
+        }
+    }).await;
+
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(attempts, vec![1, 2, 3]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_action_condition() {
+    use async_retry::{RetryAction, TestClock};
+
+    // Fails with "busy", then "throttled", then "fatal"
+    let attempts = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_secs(1)).take(5);
+    let clock = TestClock::new();
+
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            let message = match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 => "busy",
+                1 => "throttled",
+                _ => "fatal",
+            };
+            Err::<u32, TestError>(TestError(message.to_string()))
+        }
+    })
+        .with_action_condition(|e: &TestError| match e.0.as_str() {
+            "busy" => RetryAction::Retry,
+            "throttled" => RetryAction::RetryAfter(Duration::from_secs(30)),
+            _ => RetryAction::Stop,
+        })
+        .with_clock(clock.clone())
+        .await;
+
+    assert_eq!(result.unwrap_err(), TestError("fatal".to_string()));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    // 1s from the strategy, then 30s from `RetryAfter`
+    assert_eq!(clock.elapsed(), Duration::from_secs(31));
+}
+
+#[tokio::test]
+async fn test_poll_until_ready() {
+    let calls = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(10);
+
+    let calls_clone = calls.clone();
+    let result = async_retry::poll_until(
+        strategy,
+        move || {
+            let calls = calls_clone.clone();
+            async move { Ok::<u32, TestError>(calls.fetch_add(1, Ordering::SeqCst) + 1) }
+        },
+        |count| *count >= 3,
+    ).await;
+
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+impl From<async_retry::NotReady> for TestError {
+    fn from(e: async_retry::NotReady) -> Self {
+        TestError(e.to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_poll_until_fails_when_exhausted_before_ready() {
+    static SCRIPT: [Option<bool>; 3] = [Some(false), Some(false), Some(false)];
+    let calls = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(2);
+
+    let result = async_retry::poll_until(strategy, scripted_poll(&SCRIPT, calls.clone()), |ready| *ready).await;
+
+    assert_eq!(result, Err(TestError("value not ready after 3 polls".to_string())));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_poll_until_propagates_error() {
+    let op = Op::new(10, "fail");
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(10);
+
+    let op_clone = op.clone();
+    let result = async_retry::poll_until(
+        strategy,
+        move || {
+            let op = op_clone.clone();
+            async move { op.run().await }
+        },
+        |_| true,
+    ).await;
+
+    assert_eq!(result.unwrap_err(), TestError("fail".to_string()));
+    assert_eq!(op.attempts(), 1);
+}
+
+// Polls a scripted sequence of outcomes: `None` is an error, `Some(ready)` a value
+fn scripted_poll(
+    script: &'static [Option<bool>],
+    calls: Arc<AtomicU32>,
+) -> impl FnMut() -> std::future::Ready<Result<bool, TestError>> {
+    move || {
+        let call = calls.fetch_add(1, Ordering::SeqCst) as usize;
+        std::future::ready(script[call].ok_or_else(|| TestError("fail".to_string())))
+    }
+}
+
+#[tokio::test]
+async fn test_poll_max_consecutive_failures_resets_on_success() {
+    // Four errors in total, but never three in a row
+    static SCRIPT: [Option<bool>; 7] = [None, None, Some(false), None, None, Some(false), Some(true)];
+    let calls = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(10);
+
+    let result = async_retry::poll_until(strategy, scripted_poll(&SCRIPT, calls.clone()), |ready| *ready)
+        .with_max_consecutive_failures(3)
+        .await;
+
+    assert_eq!(result, Ok(true));
+    assert_eq!(calls.load(Ordering::SeqCst), 7);
+}
+
+#[tokio::test]
+async fn test_poll_max_consecutive_failures_stops_at_limit() {
+    static SCRIPT: [Option<bool>; 5] = [Some(false), None, None, None, Some(true)];
+    let calls = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(10);
+
+    let result = async_retry::poll_until(strategy, scripted_poll(&SCRIPT, calls.clone()), |ready| *ready)
+        .with_max_consecutive_failures(3)
+        .await;
+
+    assert_eq!(result, Err(TestError("fail".to_string())));
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+#[derive(Debug, PartialEq)]
+enum PanickyError {
+    Panicked(Option<String>),
+}
+
+impl std::fmt::Display for PanickyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl From<async_retry::PanicError> for PanickyError {
+    fn from(e: async_retry::PanicError) -> Self {
+        PanickyError::Panicked(e.message().map(str::to_string))
+    }
+}
+
+#[tokio::test]
+async fn test_catch_unwind_recovers_from_panic() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let attempts_clone = attempts.clone();
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(3);
+
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                panic!("transient glitch");
+            }
+            Ok::<_, PanickyError>("recovered")
+        }
+    })
+    .with_catch_unwind()
+    .await;
+
+    assert_eq!(result, Ok("recovered"));
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_catch_unwind_reports_panic_message() {
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(1);
+
+    let result: Result<(), PanickyError> = Retry::new(strategy, || async { panic!("always broken") })
+        .with_catch_unwind()
+        .await;
+
+    assert_eq!(result, Err(PanickyError::Panicked(Some("always broken".to_string()))));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_duration_fraction_of_parent_deadline() {
+    use async_retry::{Clock, TestClock};
+
+    let op = Op::new(100, "fail");
+    let strategy = FixedDelay::new(Duration::from_millis(100));
+    let clock = TestClock::new();
+    let parent_deadline = clock.now() + Duration::from_secs(1);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_clock(clock.clone())
+        .with_duration_fraction(parent_deadline, 0.5)
+        .await;
+
+    assert!(result.is_err());
+    // Capped at 500ms: five 100ms delays, then the sixth would overrun
+    assert_eq!(clock.elapsed(), Duration::from_millis(500));
+    assert_eq!(op.attempts(), 6);
+}
+
+const RETRY_INTERVAL: FixedDelay = FixedDelay::from_millis(5);
+
+#[tokio::test]
+async fn test_const_fixed_delay() {
+    use async_retry::backoff::ExponentialBackoff;
+
+    const EXPONENTIAL: ExponentialBackoff =
+        ExponentialBackoff::new(Duration::from_millis(1)).with_max_retries(2);
+    assert_eq!(EXPONENTIAL.collect::<Vec<_>>().len(), 2);
+
+    let op = Op::new(3, "fail");
+    let op_clone = op.clone();
+    let result = Retry::new(RETRY_INTERVAL.take(5), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    }).await;
+
+    assert_eq!(result, Ok(3));
+    assert_eq!(op.attempts(), 3);
+}
+
+#[tokio::test]
+async fn test_retry_all_collects_results_in_order() {
+    let ops = [Op::new(2, "a"), Op::new(1, "b"), Op::new(3, "c"), Op::new(10, "d")];
+    let strategy = FixedDelay::new(Duration::from_millis(50)).take(2);
+
+    let start = Instant::now();
+    let results = async_retry::retry_all(
+        strategy,
+        ops.iter().map(|op| move || op.run()),
+    ).await;
+
+    assert_eq!(
+        results,
+        vec![Ok(2), Ok(1), Ok(3), Err(TestError("d".to_string()))]
+    );
+    let attempts: Vec<_> = ops.iter().map(Op::attempts).collect();
+    assert_eq!(attempts, vec![2, 1, 3, 3]);
+    // Run concurrently: 100ms of delays in total, rather than 250ms in sequence
+    assert!(start.elapsed() < Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn test_no_delay_strategy() {
+    use async_retry::backoff::NoDelay;
+
+    let op = Op::new(3, "fail");
+    let op_clone = op.clone();
+    let start = Instant::now();
+    let result = Retry::new(NoDelay::with_max_retries(5), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    }).await;
+
+    assert_eq!(result, Ok(3));
+    assert_eq!(op.attempts(), 3);
+    assert!(start.elapsed() < Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn test_validator_retries_invalid_values() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let attempts_clone = attempts.clone();
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(5);
+
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            // Two empty bodies, then the expected data
+            match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Ok::<_, TestError>(String::new()),
+                _ => Ok("payload".to_string()),
+            }
+        }
+    })
+    .with_validator(|body: &String| !body.is_empty())
+    .await;
+
+    assert_eq!(result, Ok("payload".to_string()));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+impl From<async_retry::InvalidValue> for TestError {
+    fn from(e: async_retry::InvalidValue) -> Self {
+        TestError(e.to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_validator_fails_when_exhausted_on_invalid_values() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let attempts_clone = attempts.clone();
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(2);
+
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move { Ok::<_, TestError>(attempts.fetch_add(1, Ordering::SeqCst) + 1) }
+    })
+    .with_validator(|value: &u32| *value > 5)
+    .await;
+
+    // Every value is rejected, so giving up is an error
+    assert_eq!(result, Err(TestError("invalid value returned on attempt 3".to_string())));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_validator_returns_last_error_when_exhausted() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let attempts_clone = attempts.clone();
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(2);
+
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            // An error, then empty bodies
+            match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(TestError("unavailable".to_string())),
+                _ => Ok(String::new()),
+            }
+        }
+    })
+    .with_validator(|body: &String| !body.is_empty())
+    .await;
+
+    assert_eq!(result, Err(TestError("unavailable".to_string())));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_validator_rejections_count_as_circuit_failures() {
+    use async_retry::circuit::{CircuitBreaker, CircuitState};
+
+    let breaker = Arc::new(CircuitBreaker::new(3, Duration::from_secs(60)));
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(2);
+
+    let result = Retry::new(strategy, || async { Ok::<_, TestError>(String::new()) })
+        .with_circuit_breaker(breaker.clone())
+        .with_validator(|body: &String| !body.is_empty())
+        .await;
+
+    // Three rejected values are three failures, which open the circuit
+    assert_eq!(result, Err(TestError("invalid value returned on attempt 3".to_string())));
+    assert_eq!(breaker.state(), CircuitState::Open);
+}
+
+#[tokio::test]
+async fn test_hard_timeout_cancels_retry() {
+    let op = Op::new(3, "fail"); // Would succeed on attempt 3, after 200ms of delays
+    let strategy = FixedDelay::new(Duration::from_millis(100)).take(5);
+
+    let op_clone = op.clone();
+    let start = Instant::now();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_hard_timeout(Duration::from_millis(150))
+        .await;
+
+    let timeout = result.unwrap_err();
+    assert_eq!(timeout.timeout(), Duration::from_millis(150));
+    assert_eq!(op.attempts(), 2);
+    assert!(start.elapsed() < Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn test_hard_timeout_returns_result_in_time() {
+    let op = Op::new(2, "fail");
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_hard_timeout(Duration::from_secs(5))
+        .await;
+
+    assert_eq!(result, Ok(Ok(2)));
+}
+
+#[tokio::test]
+async fn test_budget_passed_to_each_attempt() {
+    use std::sync::Mutex;
+
+    let budgets = Arc::new(Mutex::new(Vec::new()));
+    let strategy = FixedDelay::new(Duration::from_millis(20)).take(3);
+
+    let budgets_clone = budgets.clone();
+    let result = Retry::with_budget(strategy, Duration::from_secs(5), move |remaining| {
+        budgets_clone.lock().unwrap().push(remaining);
+        async move { Err::<(), _>(TestError("fail".to_string())) }
+    })
+        .await;
+
+    assert!(result.is_err());
+    let budgets = budgets.lock().unwrap();
+    assert_eq!(budgets.len(), 4);
+    assert_eq!(budgets[0], Duration::from_secs(5));
+    // Each attempt sees less budget than the one before, by at least the delay
+    for pair in budgets.windows(2) {
+        assert!(pair[1] + Duration::from_millis(20) <= pair[0]);
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_budget_measured_with_configured_clock() {
+    use async_retry::TestClock;
+    use std::sync::Mutex;
+
+    let clock = TestClock::new();
+    let op_clock = clock.clone();
+    let budgets = Arc::new(Mutex::new(Vec::new()));
+    let strategy = FixedDelay::new(Duration::from_secs(5));
+
+    // Each attempt takes 10 virtual seconds, and each delay 5 more
+    let budgets_clone = budgets.clone();
+    let result = Retry::with_budget(strategy, Duration::from_secs(60), move |remaining| {
+        budgets_clone.lock().unwrap().push(remaining);
+        op_clock.advance(Duration::from_secs(10));
+        async move { Err::<(), _>(TestError("fail".to_string())) }
+    })
+        .with_clock(clock)
+        .await;
+
+    assert!(result.is_err());
+    let secs: Vec<u64> = budgets.lock().unwrap().iter().map(Duration::as_secs).collect();
+    assert_eq!(secs, [60, 45, 30, 15, 0]);
+}
+
+#[tokio::test]
+async fn test_idempotency_key_shared_by_attempts() {
+    use std::sync::Mutex;
+
+    let generated = Arc::new(AtomicU32::new(0));
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(3);
+
+    let generated_clone = generated.clone();
+    let keys_clone = keys.clone();
+    let result = Retry::with_idempotency_key(
+        strategy,
+        move || format!("key-{}", generated_clone.fetch_add(1, Ordering::SeqCst)),
+        move |key| {
+            keys_clone.lock().unwrap().push(key.to_string());
+            async move { Err::<(), _>(TestError("fail".to_string())) }
+        },
+    )
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(generated.load(Ordering::SeqCst), 1);
+    assert_eq!(*keys.lock().unwrap(), vec!["key-0"; 4]);
+}
+
+#[tokio::test]
+async fn test_wakeup_signal_cuts_sleep_short() {
+    let op = Op::new(2, "fail");
+    let strategy = FixedDelay::new(Duration::from_secs(10)).take(3);
+
+    let op_clone = op.clone();
+    let start = Instant::now();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_wakeup_signal(tokio::time::sleep(Duration::from_millis(50)))
+        .await;
+
+    // The second attempt started when the signal fired, not after 10s
+    assert_eq!(result, Ok(2));
+    assert_eq!(op.attempts(), 2);
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(50));
+    assert!(elapsed < Duration::from_secs(1));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_run_wrapped_reports_attempts() {
+    use async_retry::TestClock;
+    use std::error::Error;
+
+    let op = Op::new(10, "connection refused");
+    let strategy = FixedDelay::new(Duration::from_millis(800)).take(4);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_clock(TestClock::new())
+        .run_wrapped()
+        .await;
+
+    let error = result.unwrap_err();
+    assert_eq!(error.attempts(), 5);
+    assert_eq!(error.elapsed(), Duration::from_millis(3200));
+    assert_eq!(
+        error.to_string(),
+        "failed after 5 attempts in 3.2s: connection refused"
+    );
+
+    // The original error is the source, and can be taken back out
+    let source = error.source().unwrap();
+    assert_eq!(source.to_string(), "connection refused");
+    assert!(source.downcast_ref::<TestError>().is_some());
+    assert_eq!(error.into_inner(), TestError("connection refused".to_string()));
+}
+
+#[tokio::test]
+async fn test_run_wrapped_passes_success_through() {
+    let op = Op::new(2, "fail");
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(3);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .run_wrapped()
+        .await;
+
+    assert_eq!(result, Ok(2));
+}
+
+#[cfg(feature = "tokio-timer")]
+#[tokio::test]
+async fn test_background_upgrade_delivers_full_value() {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let attempts = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    // Every call succeeds with its attempt number; only 3 and up are complete
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move { Ok::<_, TestError>(attempts.fetch_add(1, Ordering::SeqCst) + 1) }
+    })
+        .with_background_upgrade(
+            |value: &u32| *value >= 3,
+            move |value| {
+                tx.send(value).unwrap();
+            },
+        )
+        .await;
+
+    // The partial value is returned immediately
+    assert_eq!(result, Ok(1));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    let upgraded = tokio::time::timeout(Duration::from_secs(1), rx).await;
+    assert_eq!(upgraded.unwrap(), Ok(3));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[cfg(feature = "tokio-timer")]
+#[tokio::test]
+async fn test_background_upgrade_continues_attempt_count() {
+    let (tx, rx) = tokio::sync::oneshot::channel::<u32>();
+    let attempts = Arc::new(AtomicU32::new(0));
+    let strategy = FixedDelay::new(Duration::from_millis(10));
+
+    // Only attempt 5 and up would be complete, past the 3-attempt limit
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move { Ok::<_, TestError>(attempts.fetch_add(1, Ordering::SeqCst) + 1) }
+    })
+        .with_max_attempts(3)
+        .with_background_upgrade(
+            |value: &u32| *value >= 5,
+            move |value| {
+                tx.send(value).unwrap();
+            },
+        )
+        .await;
+    assert_eq!(result, Ok(1));
+
+    // The background phase shares the limit, so the upgrade never arrives
+    assert!(rx.await.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[cfg(feature = "tokio-timer")]
+#[tokio::test]
+async fn test_background_upgrade_returns_error_without_value() {
+    let op = Op::new(10, "fail"); // Never succeeds within the schedule
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(2);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_background_upgrade(|_: &u32| true, |_| unreachable!())
+        .await;
+
+    assert_eq!(result, Err(TestError("fail".to_string())));
+    assert_eq!(op.attempts(), 3);
+}
+
+#[tokio::test]
+async fn test_forever_retries_until_stopped() {
+    use std::sync::atomic::AtomicBool;
+
+    // Retries past any usual limit, until it succeeds
+    let op = Op::new(20, "fail");
+    let op_clone = op.clone();
+    let result = Retry::forever(FixedDelay::new(Duration::from_millis(1)), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_duration(Duration::from_secs(5))
+        .await;
+    assert_eq!(result, Ok(20));
+
+    // Stops once aborted, with the last error
+    let flag = Arc::new(AtomicBool::new(false));
+    let op = Op::new(u32::MAX, "fail");
+    let op_clone = op.clone();
+    let flag_clone = flag.clone();
+    let result = Retry::forever(FixedDelay::new(Duration::from_millis(1)), move || {
+        let op = op_clone.clone();
+        let flag = flag_clone.clone();
+        async move {
+            if op.attempts() == 9 {
+                flag.store(true, Ordering::SeqCst);
+            }
+            op.run().await
+        }
+    })
+        .with_abort_flag(flag)
+        .await;
+    assert_eq!(result, Err(TestError("fail".to_string())));
+    assert_eq!(op.attempts(), 10);
+
+    // Or cancelled as a whole
+    let result = Retry::forever(FixedDelay::new(Duration::from_millis(10)), || async {
+        Err::<(), _>(TestError("fail".to_string()))
+    })
+        .with_hard_timeout(Duration::from_millis(50))
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_schedule_from_slice() {
+    use async_retry::backoff::Schedule;
+
+    let delays = [10, 20, 30].map(Duration::from_millis);
+    let strategy = Schedule::from_iter(delays.iter().copied());
+    assert_eq!(strategy.len(), 3);
+
+    // One retry per delay, then the last error
+    let op = Op::new(10, "fail");
+    let op_clone = op.clone();
+    let start = Instant::now();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .await;
+
+    assert_eq!(result, Err(TestError("fail".to_string())));
+    assert_eq!(op.attempts(), 4);
+    assert!(start.elapsed() >= Duration::from_millis(60));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_on_complete_fires_once() {
+    use async_retry::TestClock;
+    use std::sync::Mutex;
+
+    // (succeeded, attempts, elapsed) for each call of the hook
+    let calls = Arc::new(Mutex::new(Vec::new()));
+
+    for succeed_on in [2, 10] {
+        let op = Op::new(succeed_on, "fail");
+        let strategy = FixedDelay::new(Duration::from_millis(100)).take(3);
+
+        let op_clone = op.clone();
+        let calls_clone = calls.clone();
+        let result = Retry::new(strategy, move || {
+            let op = op_clone.clone();
+            async move { op.run().await }
+        })
+            .with_clock(TestClock::new())
+            .with_on_complete(move |result, attempts, elapsed| {
+                calls_clone.lock().unwrap().push((result.is_ok(), attempts, elapsed));
+            })
+            .await;
+        assert_eq!(result.is_ok(), succeed_on == 2);
+    }
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            (true, 2, Duration::from_millis(100)),
+            (false, 4, Duration::from_millis(300)),
+        ]
+    );
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_delay_transform_rounds_up() {
+    use async_retry::{backoff::FromSchedule, TestClock};
+
+    let op = Op::new(10, "fail");
+    let strategy = FromSchedule::new([
+        Duration::from_millis(30),
+        Duration::from_millis(100),
+        Duration::from_millis(250),
+    ]);
+    let seen_attempts = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen_clone = seen_attempts.clone();
+
+    let op_clone = op.clone();
+    let (result, stats) = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_clock(TestClock::new())
+        .with_record_delays()
+        .with_delay_transform(move |delay, attempt| {
+            seen_clone.lock().unwrap().push(attempt);
+            // Round up to the next 100ms bucket
+            Duration::from_millis(delay.as_millis().div_ceil(100) as u64 * 100)
+        })
+        .run_with_stats()
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(
+        stats.delays,
+        Some(vec![
+            Duration::from_millis(100),
+            Duration::from_millis(100),
+            Duration::from_millis(300),
+        ])
+    );
+    assert_eq!(*seen_attempts.lock().unwrap(), vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_run_into_converts_final_error() {
+    async fn load(op: Op) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
+        let strategy = FixedDelay::new(Duration::from_millis(1)).take(2);
+        let value = Retry::new(strategy, move || {
+            let op = op.clone();
+            async move { op.run().await }
+        })
+            // The condition still sees the concrete error type
+            .with_condition(|e: &TestError| e.0 == "fail")
+            .run_into::<Box<dyn std::error::Error + Send + Sync>>()
+            .await?;
+        Ok(value)
+    }
+
+    assert_eq!(load(Op::new(2, "fail")).await.unwrap(), 2);
+
+    let error = load(Op::new(10, "fail")).await.unwrap_err();
+    assert_eq!(error.to_string(), "fail");
+    assert!(error.downcast_ref::<TestError>().is_some());
+}
+
+#[tokio::test]
+async fn test_degraded_threshold_flags_late_success() {
+    async fn run(succeed_on: u32) -> async_retry::RetryStats {
+        let op = Op::new(succeed_on, "fail");
+        let strategy = FixedDelay::new(Duration::from_millis(1)).take(5);
+        let (result, stats) = Retry::new(strategy, move || {
+            let op = op.clone();
+            async move { op.run().await }
+        })
+            .with_degraded_threshold(2)
+            .run_with_stats()
+            .await;
+        assert!(result.is_ok());
+        stats
+    }
+
+    assert!(!run(1).await.degraded);
+    assert!(!run(2).await.degraded);
+    assert!(run(3).await.degraded);
+}
+
+#[cfg(feature = "logging")]
+#[tokio::test]
+async fn test_degraded_threshold_logs_warning() {
+    capture::install();
+
+    for (name, succeed_on) in [("degraded-fast", 2), ("degraded-slow", 3)] {
+        let op = Op::new(succeed_on, "fail");
+        let strategy = FixedDelay::new(Duration::from_millis(1)).take(5);
+        let result = Retry::new(strategy, move || {
+            let op = op.clone();
+            async move { op.run().await }
+        })
+            .with_operation_name(name)
+            .with_degraded_threshold(2)
+            .await;
+        assert!(result.is_ok());
+    }
+
+    let degraded = |name| {
+        capture::records_for(name)
+            .into_iter()
+            .filter(|fields| fields.contains_key("degraded_threshold"))
+            .collect::<Vec<_>>()
+    };
+    assert!(degraded("degraded-fast").is_empty());
+    let slow = degraded("degraded-slow");
+    assert_eq!(slow.len(), 1);
+    assert_eq!(slow[0]["attempt"], "3");
+    assert_eq!(slow[0]["degraded_threshold"], "2");
+}
+
+#[tokio::test]
+async fn test_boxed_dyn_future_operation() {
+    use std::future::Future;
+    use std::pin::Pin;
+
+    type BoxedOp = Pin<Box<dyn Future<Output = Result<u32, TestError>> + Send>>;
+
+    // Plugins chosen at runtime, each with its own future type
+    fn plugin(attempt: u32) -> BoxedOp {
+        if attempt < 2 {
+            Box::pin(async { Err(TestError("flaky plugin".to_string())) })
+        } else {
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                Ok(attempt)
+            })
+        }
+    }
+
+    let attempts = Arc::new(AtomicU32::new(0));
+    let attempts_clone = attempts.clone();
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(5);
+
+    let result = Retry::new(strategy, move || plugin(attempts_clone.fetch_add(1, Ordering::SeqCst))).await;
+
+    assert_eq!(result, Ok(2));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+impl From<async_retry::RetryTimeout> for TestError {
+    fn from(e: async_retry::RetryTimeout) -> Self {
+        TestError(e.to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_strict_max_duration_cuts_off_slow_attempt() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let attempts_clone = attempts.clone();
+    let strategy = FixedDelay::new(Duration::from_millis(10));
+
+    let start = Instant::now();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Ok::<_, TestError>(())
+        }
+    })
+        .with_strict_max_duration(Duration::from_millis(100))
+        .await;
+
+    let elapsed = start.elapsed();
+    assert_eq!(result, Err(TestError("retry timed out after 100ms".to_string())));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    assert!(elapsed >= Duration::from_millis(100) && elapsed < Duration::from_millis(250));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_strict_max_duration_uses_configured_clock() {
+    use async_retry::TestClock;
+
+    let clock = TestClock::new();
+    let op_clock = clock.clone();
+    let strategy = FixedDelay::new(Duration::from_secs(1));
+
+    // The attempt spends 40 of the 60 virtual seconds, then hangs
+    let result = Retry::new(strategy, move || {
+        let clock = op_clock.clone();
+        async move {
+            clock.advance(Duration::from_secs(40));
+            std::future::pending::<Result<(), TestError>>().await
+        }
+    })
+        .with_strict_max_duration(Duration::from_secs(60))
+        .with_clock(clock.clone())
+        .await;
+
+    assert_eq!(result, Err(TestError("retry timed out after 60s".to_string())));
+    assert_eq!(clock.elapsed(), Duration::from_secs(60));
+}
+
+#[tokio::test]
+async fn test_strict_max_duration_restarts_on_each_run() {
+    let strategy = FixedDelay::new(Duration::from_millis(10));
+    let mut retry = Retry::new(strategy, ()).with_strict_max_duration(Duration::from_millis(100));
+
+    // The first run spends its whole budget on one slow attempt.
+    let result = retry.run_with(|| async {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        Ok::<_, TestError>(())
+    }).await;
+    assert_eq!(result, Err(TestError("retry timed out after 100ms".to_string())));
+
+    // The second run gets a fresh budget.
+    let result = retry.run_with(|| async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Ok::<_, TestError>(())
+    }).await;
+    assert_eq!(result, Ok(()));
+}
+
+#[tokio::test]
+async fn test_custom_sleep_fn_records_delays() {
+    use async_retry::backoff::ExponentialBackoff;
+    use std::sync::Mutex;
+
+    let op = Op::new(10, "fail"); // Never succeeds within the schedule
+    let strategy = ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(3);
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let recorded = delays.clone();
+
+    let start = Instant::now();
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_sleep_fn(move |delay| {
+            recorded.lock().unwrap().push(delay);
+            std::future::ready(())
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(op.attempts(), 4);
+    assert_eq!(
+        *delays.lock().unwrap(),
+        [Duration::from_secs(1), Duration::from_secs(2), Duration::from_secs(4)]
+    );
+    // The fake sleep never actually waits
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_stats_split_sleep_and_exec_time() {
+    use async_retry::{backoff::ExponentialBackoff, TestClock};
+
+    let op = Op::new(10, "fail"); // Never succeeds within the schedule
+    let strategy = ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(3);
+    let clock = TestClock::new();
+
+    let (result, stats) = Retry::new(strategy, move || {
+        let op = op.clone();
+        async move { op.run().await }
+    })
+        .with_clock(clock.clone())
+        .run_with_stats()
+        .await;
+
+    assert!(result.is_err());
+    // 1s + 2s + 4s of virtual sleep, and the operation takes no virtual time
+    assert_eq!(stats.total_sleep, Duration::from_secs(7));
+    assert_eq!(stats.total_exec, Duration::ZERO);
+    assert_eq!(clock.elapsed(), stats.total_sleep);
+}
+
+#[tokio::test]
+async fn test_latency_aware_delay_waits_for_slow_operation() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let attempts_clone = attempts.clone();
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(1);
+
+    let (result, stats) = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                // A slow failure, then a fast success
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                return Err(TestError("slow".to_string()));
+            }
+            Ok(())
+        }
+    })
+        .with_latency_aware_delay()
+        .with_record_delays()
+        .run_with_stats()
+        .await;
+
+    assert!(result.is_ok());
+    let delays = stats.delays.unwrap();
+    assert_eq!(delays.len(), 1);
+    // The nominal 10ms delay was raised to the 100ms the attempt took
+    assert!(delays[0] >= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_with_enabled_false_makes_single_attempt() {
+    let op = Op::new(3, "fail");
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_enabled(false)
+        .await;
+
+    assert_eq!(result, Err(TestError("fail".to_string())));
+    assert_eq!(op.attempts(), 1);
+}
+
+#[tokio::test]
+async fn test_spin_guard_stops_zero_delay_strategy() {
+    let op = Op::new(u32::MAX, "fail"); // Never succeeds
+
+    // A pathological strategy: no delay, and it never ends
+    let strategy = std::iter::repeat(Duration::ZERO);
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_spin_guard(10)
+        .await;
+
+    assert!(result.is_err());
+    // The first attempt, then 10 immediate retries
+    assert_eq!(op.attempts(), 11);
+}
+
+#[cfg(feature = "logging")]
+#[tokio::test]
+async fn test_log_level_applies_to_attempt_failures() {
+    capture::install();
+
+    for (name, level) in [("level-default", None), ("level-debug", Some(log::Level::Debug))] {
+        let op = Op::new(2, "fail"); // Fails once
+        let strategy = FixedDelay::new(Duration::from_millis(1)).take(5);
+        let retry = Retry::new(strategy, move || {
+            let op = op.clone();
+            async move { op.run().await }
+        })
+            .with_operation_name(name);
+        let result = match level {
+            Some(level) => retry.with_log_level(level).await,
+            None => retry.await,
+        };
+        assert!(result.is_ok());
+    }
+
+    let failure_level = |name| {
+        capture::records_for(name)
+            .into_iter()
+            .find(|fields| fields.contains_key("error_display"))
+            .expect("failure record")["level"]
+            .clone()
+    };
+    assert_eq!(failure_level("level-default"), "WARN");
+    assert_eq!(failure_level("level-debug"), "DEBUG");
+}
+
+// Runs an always-failing operation with both a retry limit on the strategy
+// and a maximum duration, on a virtual clock. Returns the final error, the
+// number of attempts and the virtual time spent. The error is prefixed with
+// "deadline: " only if the maximum duration is what stopped the loop.
+#[cfg(feature = "test-util")]
+async fn run_with_both_limits(
+    strategy: impl async_retry::Backoff + Send + 'static,
+    max_duration: Duration,
+) -> (TestError, u32, Duration) {
+    use async_retry::TestClock;
+
+    let op = Op::new(u32::MAX, "fail"); // Never succeeds
+    let clock = TestClock::new();
+
+    let op_clone = op.clone();
+    let result = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .with_max_duration(max_duration)
+        .with_timeout_error(|last: TestError| TestError(format!("deadline: {}", last)))
+        .with_clock(clock.clone())
+        .await;
+
+    (result.unwrap_err(), op.attempts(), clock.elapsed())
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_max_duration_fires_before_max_retries() {
+    use async_retry::backoff::ExponentialBackoff;
+
+    // Attempts at 0s, 1s and 3s; the next 4s delay would end at 7s > 5s.
+    let strategy = ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(10);
+    let (error, attempts, elapsed) = run_with_both_limits(strategy, Duration::from_secs(5)).await;
+
+    assert_eq!(error, TestError("deadline: fail".to_string()));
+    assert_eq!(attempts, 3);
+    assert_eq!(elapsed, Duration::from_secs(3));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_max_retries_fires_before_max_duration() {
+    use async_retry::backoff::ExponentialBackoff;
+
+    // Attempts at 0s, 1s and 3s, then the strategy is exhausted.
+    let strategy = ExponentialBackoff::new(Duration::from_secs(1)).with_max_retries(2);
+    let (error, attempts, elapsed) = run_with_both_limits(strategy, Duration::from_secs(60)).await;
+
+    assert_eq!(error, TestError("fail".to_string()));
+    assert_eq!(attempts, 3);
+    assert_eq!(elapsed, Duration::from_secs(3));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_max_duration_boundary_allows_delay_ending_exactly_at_limit() {
+    // Delays ending exactly at the limit are slept, and the attempt made at
+    // the limit still runs; the loop stops after it fails.
+    let strategy = FixedDelay::new(Duration::from_secs(1)).take(10);
+    let (error, attempts, elapsed) = run_with_both_limits(strategy, Duration::from_secs(3)).await;
+
+    assert_eq!(error, TestError("deadline: fail".to_string()));
+    assert_eq!(attempts, 4);
+    assert_eq!(elapsed, Duration::from_secs(3));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_limits_reached_together_report_max_duration() {
+    // The last retry the strategy allows happens exactly at the limit.
+    let strategy = FixedDelay::new(Duration::from_secs(1)).take(3);
+    let (error, attempts, elapsed) = run_with_both_limits(strategy, Duration::from_secs(3)).await;
+
+    assert_eq!(error, TestError("deadline: fail".to_string()));
+    assert_eq!(attempts, 4);
+    assert_eq!(elapsed, Duration::from_secs(3));
+}
+
+#[cfg(feature = "tokio-timer")]
+#[tokio::test]
+async fn test_retry_handle_reports_progress_mid_run() {
+    let op = Op::new(3, "still down");
+    let strategy = FixedDelay::new(Duration::from_millis(100)).take(5);
+
+    let op_clone = op.clone();
+    let (task, handle) = Retry::new(strategy, move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .spawn_with_handle();
+
+    // Partway through the backoff after the first failure
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(handle.attempts_made(), 1);
+    assert_eq!(handle.last_error().as_deref(), Some("still down"));
+
+    assert_eq!(task.await.unwrap(), Ok(3));
+    assert_eq!(handle.attempts_made(), 3);
+    // The last failure is kept after the run succeeds
+    assert_eq!(handle.last_error().as_deref(), Some("still down"));
+}
+
+#[cfg(feature = "tokio-timer")]
+#[tokio::test]
+async fn test_countdown_channel_publishes_each_delay() {
+    use async_retry::backoff::ExponentialBackoff;
+    use std::sync::Mutex;
+
+    let (tx, rx) = tokio::sync::watch::channel(None);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let attempts = Arc::new(AtomicU32::new(0));
+
+    // Each attempt records the countdown that preceded it
+    let seen_clone = seen.clone();
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(
+        ExponentialBackoff::new(Duration::from_millis(10)).with_max_retries(5),
+        move || {
+            let countdown = *rx.borrow();
+            seen_clone.lock().unwrap().push(countdown);
+            let attempt = attempts_clone.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                if attempt < 4 {
+                    Err(TestError("unavailable".to_string()))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        },
+    )
+        .with_countdown_channel(tx)
+        .await;
+    assert_eq!(result, Ok(4));
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 4);
+    assert_eq!(seen[0], None);
+    let published: Vec<_> = seen[1..]
+        .iter()
+        .map(|countdown| {
+            let countdown = countdown.unwrap();
+            (countdown.attempt, countdown.delay)
+        })
+        .collect();
+    assert_eq!(
+        published,
+        vec![
+            (1, Duration::from_millis(10)),
+            (2, Duration::from_millis(20)),
+            (3, Duration::from_millis(40)),
+        ]
+    );
+
+    // Each countdown is fully elapsed by the time the next attempt runs
+    let first = seen[1].unwrap();
+    assert!(first.started_at < seen[2].unwrap().started_at);
+    assert_eq!(first.remaining_at(seen[2].unwrap().started_at), Duration::ZERO);
+    assert_eq!(first.remaining_at(first.started_at), Duration::from_millis(10));
+}
+
+#[cfg(feature = "tracing")]
+mod span_capture {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    // Records the name of the innermost entered span for every event.
+    #[derive(Clone, Default)]
+    pub struct SpanCapture {
+        next_id: Arc<AtomicU64>,
+        names: Arc<Mutex<HashMap<u64, &'static str>>>,
+        entered: Arc<Mutex<Vec<u64>>>,
+        pub events: Arc<Mutex<Vec<Option<&'static str>>>>,
+    }
+
+    impl Subscriber for SpanCapture {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+            self.names.lock().unwrap().insert(id, span.metadata().name());
+            Id::from_u64(id)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {
+            let current = self.entered.lock().unwrap().last().copied();
+            let name = current.map(|id| self.names.lock().unwrap()[&id]);
+            self.events.lock().unwrap().push(name);
+        }
+
+        fn enter(&self, span: &Id) {
+            self.entered.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _span: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn test_with_span_records_events_under_span() {
+    let capture = span_capture::SpanCapture::default();
+    let _guard = tracing::subscriber::set_default(capture.clone());
+
+    let op = Op::new(2, "fail"); // Fails once
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(3);
+    let result = Retry::new(strategy, move || {
+        let op = op.clone();
+        tracing::info!("calling");
+        async move {
+            tokio::task::yield_now().await;
+            tracing::info!("polled");
+            op.run().await
+        }
+    })
+        .with_span(tracing::info_span!("retry_span"))
+        .await;
+    assert_eq!(result, Ok(2));
+
+    // Two attempts, each with an event when called and one when polled
+    tracing::info!("outside");
+    assert_eq!(
+        *capture.events.lock().unwrap(),
+        [Some("retry_span"), Some("retry_span"), Some("retry_span"), Some("retry_span"), None]
+    );
+}
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn test_with_span_applies_to_run() {
+    let capture = span_capture::SpanCapture::default();
+    let _guard = tracing::subscriber::set_default(capture.clone());
+
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(3);
+    let mut retry = Retry::new(strategy, ()).with_span(tracing::info_span!("retry_span"));
+    for _ in 0..2 {
+        let op = Op::new(1, "fail"); // Succeeds at once
+        let result = retry.run_with(move || {
+            let op = op.clone();
+            async move {
+                tracing::info!("polled");
+                op.run().await
+            }
+        }).await;
+        assert_eq!(result, Ok(1));
+    }
+
+    assert_eq!(*capture.events.lock().unwrap(), [Some("retry_span"), Some("retry_span")]);
+}
+
+#[tokio::test]
+async fn test_retry_with_built_strategy() {
+    use async_retry::BackoffBuilder;
+
+    let base = Duration::from_millis(5);
+    for builder in [
+        BackoffBuilder::new().fixed(base),
+        BackoffBuilder::new().exponential(base),
+        BackoffBuilder::new().fibonacci(base),
+    ] {
+        let op = Op::new(10, "fail"); // Never succeeds within the limit
+        let op_clone = op.clone();
+        let result = Retry::new(builder.max_retries(2).build(), move || {
+            let op = op_clone.clone();
+            async move { op.run().await }
+        })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(op.attempts(), 3);
+    }
+}
+
+#[tokio::test]
+async fn test_with_shared_state_for_operation_and_condition() {
+    // Both the operation and the condition read the threshold
+    struct Shared {
+        attempts: AtomicU32,
+        threshold: u32,
+    }
+
+    let shared = Arc::new(Shared { attempts: AtomicU32::new(0), threshold: 3 });
+    let strategy = FixedDelay::new(Duration::from_millis(1)).take(10);
+
+    let result = Retry::with_shared(
+        strategy,
+        shared.clone(),
+        |shared| async move {
+            let attempt = shared.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            Err::<(), _>(TestError(attempt.to_string()))
+        },
+        |shared, error: &TestError| error.0.parse::<u32>().unwrap() < shared.threshold,
+    )
+        .await;
+
+    assert_eq!(result, Err(TestError("3".to_string())));
+    assert_eq!(shared.attempts.load(Ordering::SeqCst), shared.threshold);
+}
+
+#[tokio::test]
+async fn test_at_least_once_rejects_strategy_without_retries() {
+    use async_retry::backoff::{BackoffError, ExponentialBackoff};
+
+    let operation = || async { Err::<(), _>(TestError("fail".to_string())) };
+    let no_retries = Retry::new(FixedDelay::new(Duration::from_millis(1)).take(0), operation);
+    assert_eq!(no_retries.at_least_once().err(), Some(BackoffError::NoRetries));
+
+    let no_retries = Retry::new(ExponentialBackoff::new(Duration::from_millis(1)).with_max_retries(0), operation);
+    assert_eq!(no_retries.at_least_once().err(), Some(BackoffError::NoRetries));
+
+    // A strategy with a retry passes, and the operation is retried
+    let op = Op::new(2, "fail");
+    let op_clone = op.clone();
+    let retry = Retry::new(FixedDelay::new(Duration::from_millis(1)).take(1), move || {
+        let op = op_clone.clone();
+        async move { op.run().await }
+    })
+        .at_least_once()
+        .expect("strategy allows a retry");
+    assert_eq!(retry.await, Ok(2));
+    assert_eq!(op.attempts(), 2);
+}
+
+// The timeout features must behave the same on async-std as on Tokio.
+#[cfg(feature = "async-std-timer")]
+#[test]
+fn test_hard_timeout_on_async_std() {
+    async_std::task::block_on(async {
+        let op = Op::new(3, "fail"); // Would succeed on attempt 3, after 200ms of delays
+        let strategy = FixedDelay::new(Duration::from_millis(100)).take(5);
+
+        let op_clone = op.clone();
+        let start = Instant::now();
+        let result = Retry::new(strategy, move || {
+            let op = op_clone.clone();
+            async move { op.run().await }
+        })
+            .with_hard_timeout(Duration::from_millis(150))
+            .await;
+
+        assert_eq!(result.unwrap_err().timeout(), Duration::from_millis(150));
+        assert_eq!(op.attempts(), 2);
+        assert!(start.elapsed() < Duration::from_millis(200));
+    });
+}
+
+#[cfg(feature = "async-std-timer")]
+#[test]
+fn test_strict_max_duration_on_async_std() {
+    async_std::task::block_on(async {
+        let strategy = FixedDelay::new(Duration::from_millis(10));
+
+        let start = Instant::now();
+        let result = Retry::new(strategy, move || async move {
+            async_std::task::sleep(Duration::from_millis(500)).await;
+            Ok::<_, TestError>(())
+        })
+            .with_strict_max_duration(Duration::from_millis(100))
+            .await;
+
+        assert_eq!(result, Err(TestError("retry timed out after 100ms".to_string())));
+        assert!(start.elapsed() < Duration::from_millis(250));
+    });
+}
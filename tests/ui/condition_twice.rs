@@ -0,0 +1,10 @@
+use async_retry::{backoff::FixedDelay, Retry};
+use std::time::Duration;
+
+fn main() {
+    let _retry = Retry::new(FixedDelay::new(Duration::from_secs(1)).take(3), || async {
+        Err::<(), String>("failed".to_string())
+    })
+    .with_condition(|e: &String| e.contains("timeout"))
+    .with_condition(|e: &String| e.contains("refused"));
+}
@@ -0,0 +1,18 @@
+use async_retry::{backoff::FixedDelay, Retry, Retryable};
+use std::time::Duration;
+
+struct MyError;
+
+impl Retryable for MyError {
+    fn is_retryable(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    let _retry = Retry::new(FixedDelay::new(Duration::from_secs(1)).take(3), || async {
+        Err::<(), MyError>(MyError)
+    })
+    .with_retryable_trait()
+    .with_condition(|_: &MyError| false);
+}
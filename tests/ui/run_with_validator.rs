@@ -0,0 +1,8 @@
+use async_retry::{backoff::FixedDelay, Retry};
+use std::time::Duration;
+
+fn main() {
+    let mut retry = Retry::new(FixedDelay::new(Duration::from_secs(1)).take(3), ())
+        .with_validator(|body: &String| !body.is_empty());
+    let _result = retry.run_with(|| async { Ok::<_, String>(String::new()) });
+}
@@ -0,0 +1,46 @@
+// Author: Jacques Murray
+//
+// Browser tests for the `wasm-timer` feature. Run with:
+// wasm-pack test --headless --firefox -- --features wasm-timer
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm-timer"))]
+
+use async_retry::{backoff::FixedDelay, Retry};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, PartialEq, Eq)]
+struct TestError;
+
+impl std::fmt::Display for TestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "fail")
+    }
+}
+
+#[wasm_bindgen_test]
+async fn test_retry_with_browser_timer() {
+    let attempts = Rc::new(Cell::new(0u32));
+    let strategy = FixedDelay::new(Duration::from_millis(10)).take(5);
+
+    let attempts_clone = attempts.clone();
+    let result = Retry::new(strategy, move || {
+        let attempts = attempts_clone.clone();
+        async move {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(TestError)
+            } else {
+                Ok(attempts.get())
+            }
+        }
+    })
+        .local()
+        .await;
+
+    assert_eq!(result.unwrap(), 3);
+}